@@ -1,5 +1,8 @@
-use proc_macro2::Span;
-use syn::{punctuated::Punctuated, spanned::Spanned, Token, WherePredicate};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{ext::IdentExt, punctuated::Punctuated, spanned::Spanned, Token, WherePredicate};
+
+use crate::case::RenameRule;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -11,6 +14,18 @@ pub enum Error {
 
 	#[error("missing seed type")]
 	MissingSeed,
+
+	#[error("`content` requires a `tag` to be specified")]
+	ContentWithoutTag(Span),
+
+	#[error("`tag`, `content` and `untagged` are only supported on enums")]
+	UnexpectedTag(Span),
+
+	#[error("untagged enums cannot have a `tag`")]
+	UntaggedWithTag(Span),
+
+	#[error("`from` and `try_from` cannot be used together")]
+	FromAndTryFrom(Span),
 }
 
 impl Error {
@@ -19,17 +34,28 @@ impl Error {
 			Self::ExpectedMetaList(s) => *s,
 			Self::Parse(e) => e.span(),
 			Self::MissingSeed => Span::call_site(),
+			Self::ContentWithoutTag(s) => *s,
+			Self::UnexpectedTag(s) => *s,
+			Self::UntaggedWithTag(s) => *s,
+			Self::FromAndTryFrom(s) => *s,
 		}
 	}
 }
 
 #[derive(Default)]
 pub struct FieldAttributes {
-	pub skip: bool,
-	pub default: bool,
-	pub with: Option<syn::Path>,
+	pub skip_serializing: bool,
+	pub skip_deserializing: bool,
+	pub default: Option<FieldDefault>,
+	pub required: bool,
+	pub serialize_with: Option<FieldFn>,
+	pub deserialize_with: Option<FieldFn>,
 	pub skip_serializing_if: Option<syn::Path>,
+	pub skip_serializing_if_seeded: Option<syn::Path>,
 	pub rename: Option<String>,
+	pub flatten: bool,
+	pub seed: Option<syn::Expr>,
+	pub getter: Option<syn::Path>,
 }
 
 impl FieldAttributes {
@@ -52,33 +78,109 @@ impl FieldAttributes {
 	}
 
 	pub fn merge_with(&mut self, other: Self) {
-		self.skip |= other.skip;
-		self.default |= other.default;
+		self.skip_serializing |= other.skip_serializing;
+		self.skip_deserializing |= other.skip_deserializing;
+		if let Some(default) = other.default {
+			self.default = Some(default)
+		}
+		self.required |= other.required;
+		self.flatten |= other.flatten;
+
+		if let Some(f) = other.serialize_with {
+			self.serialize_with = Some(f)
+		}
 
-		if let Some(path) = other.with {
-			self.with = Some(path)
+		if let Some(f) = other.deserialize_with {
+			self.deserialize_with = Some(f)
 		}
 
 		if let Some(path) = other.skip_serializing_if {
 			self.skip_serializing_if = Some(path)
 		}
 
+		if let Some(path) = other.skip_serializing_if_seeded {
+			self.skip_serializing_if_seeded = Some(path)
+		}
+
 		if let Some(name) = other.rename {
 			self.rename = Some(name)
 		}
+
+		if let Some(expr) = other.seed {
+			self.seed = Some(expr)
+		}
+
+		if let Some(path) = other.getter {
+			self.getter = Some(path)
+		}
 	}
 
-	pub fn name(&self, ident: &syn::Ident) -> String {
+	pub fn name(&self, ident: &syn::Ident, rule: RenameRule) -> String {
 		match &self.rename {
 			Some(name) => name.clone(),
-			None => {
-				let name = ident.to_string();
+			None => rule.apply_to_field(&unraw(ident)),
+		}
+	}
 
-				match name.strip_prefix("r#") {
-					Some(suffix) => suffix.to_owned(),
-					None => name,
+	/// Expression computing the default value of this field, using the given
+	/// seed expression.
+	///
+	/// Fields without `default` attribute default to `Default::default()`.
+	pub fn default_value(&self, seed: TokenStream) -> TokenStream {
+		match &self.default {
+			None | Some(FieldDefault::Default) => quote! { ::core::default::Default::default() },
+			Some(FieldDefault::Path(path)) => quote! { #path() },
+			Some(FieldDefault::Seeded(path)) => quote! { #path(#seed) },
+		}
+	}
+
+	/// Condition under which this field is not serialized, given an expression
+	/// referencing the field value, and the `seed` variable.
+	pub fn skip_serializing_condition(&self, value: &TokenStream) -> Option<TokenStream> {
+		let unseeded = self
+			.skip_serializing_if
+			.as_ref()
+			.map(|path| quote! { #path(#value) });
+		let seeded = self
+			.skip_serializing_if_seeded
+			.as_ref()
+			.map(|path| quote! { #path(#value, seed) });
+
+		match (unseeded, seeded) {
+			(Some(a), Some(b)) => Some(quote! { #a || #b }),
+			(a, b) => a.or(b),
+		}
+	}
+
+	/// Expression computing the seed of this field from the `seed` variable.
+	///
+	/// The given seed components are bound by name in the expression. A
+	/// missing component returns early with a custom error built using the
+	/// given error trait.
+	pub fn seed_expr(&self, components: &[SeedComponent], error: TokenStream) -> TokenStream {
+		match &self.seed {
+			Some(expr) if !components.is_empty() => {
+				let bindings = components.iter().map(|SeedComponent { name, ty }| {
+					quote! {
+						#[allow(unused_variables)]
+						let #name = match <_ as _serde_seeded::context::Provide<#ty>>::provide(&*seed) {
+							Some(component) => component,
+							None => return Err(#error::custom(
+								::core::concat!("missing seed component `", ::core::stringify!(#ty), "`")
+							))
+						};
+					}
+				});
+
+				quote! {
+					{
+						#(#bindings)*
+						#expr
+					}
 				}
 			}
+			Some(expr) => quote! { #expr },
+			None => quote! { seed },
 		}
 	}
 }
@@ -91,15 +193,36 @@ impl syn::parse::Parse for FieldAttributes {
 			Punctuated::parse_terminated(input)?;
 		for attr in attributes {
 			match attr {
-				FieldAttribute::Skip => result.skip = true,
-				FieldAttribute::Default => result.default = true,
+				FieldAttribute::Skip => {
+					result.skip_serializing = true;
+					result.skip_deserializing = true;
+				}
+				FieldAttribute::SkipSerializing => result.skip_serializing = true,
+				FieldAttribute::SkipDeserializing => result.skip_deserializing = true,
+				FieldAttribute::Default(default) => result.default = Some(default),
+				FieldAttribute::Required => result.required = true,
 				FieldAttribute::With(path) => {
-					result.with = Some(path);
+					result.serialize_with = Some(FieldFn::Module(path.clone()));
+					result.deserialize_with = Some(FieldFn::Module(path));
+				}
+				FieldAttribute::WithUnseeded(path) => {
+					result.serialize_with =
+						Some(FieldFn::Unseeded(syn::parse_quote!(#path::serialize)));
+					result.deserialize_with =
+						Some(FieldFn::Unseeded(syn::parse_quote!(#path::deserialize)));
 				}
+				FieldAttribute::SerializeWith(f) => result.serialize_with = Some(f),
+				FieldAttribute::DeserializeWith(f) => result.deserialize_with = Some(f),
 				FieldAttribute::SkipSerializingIf(path) => {
 					result.skip_serializing_if = Some(path);
 				}
+				FieldAttribute::SkipSerializingIfSeeded(path) => {
+					result.skip_serializing_if_seeded = Some(path);
+				}
 				FieldAttribute::Rename(name) => result.rename = Some(name.value()),
+				FieldAttribute::Flatten => result.flatten = true,
+				FieldAttribute::Seed(expr) => result.seed = Some(expr),
+				FieldAttribute::Getter(path) => result.getter = Some(path),
 			}
 		}
 
@@ -107,12 +230,49 @@ impl syn::parse::Parse for FieldAttributes {
 	}
 }
 
+/// Default value of a field.
+pub enum FieldDefault {
+	/// `Default::default()`.
+	Default,
+
+	/// Function taking no argument.
+	Path(syn::Path),
+
+	/// Function taking the seed as argument.
+	Seeded(syn::Path),
+}
+
+/// Function used in place of the `SerializeSeeded` or `DeserializeSeeded`
+/// implementation of a field.
+pub enum FieldFn {
+	/// Function that does not take the seed, as found in `serde` `with`
+	/// modules.
+	Unseeded(syn::Path),
+
+	/// Function taking the seed as argument.
+	Seeded(syn::Path),
+
+	/// Module providing the functions taking the seed as argument, such as
+	/// `serialize_seeded` and `deserialize_seeded`.
+	Module(syn::Path),
+}
+
 pub enum FieldAttribute {
 	Skip,
-	Default,
+	SkipSerializing,
+	SkipDeserializing,
+	Default(FieldDefault),
+	Required,
 	With(syn::Path),
+	WithUnseeded(syn::Path),
+	SerializeWith(FieldFn),
+	DeserializeWith(FieldFn),
 	SkipSerializingIf(syn::Path),
+	SkipSerializingIfSeeded(syn::Path),
 	Rename(syn::LitStr),
+	Flatten,
+	Seed(syn::Expr),
+	Getter(syn::Path),
 }
 
 impl syn::parse::Parse for FieldAttribute {
@@ -120,20 +280,142 @@ impl syn::parse::Parse for FieldAttribute {
 		let id: syn::Ident = input.parse()?;
 		if id == "skip" {
 			Ok(Self::Skip)
+		} else if id == "skip_serializing" {
+			Ok(Self::SkipSerializing)
+		} else if id == "skip_deserializing" {
+			Ok(Self::SkipDeserializing)
 		} else if id == "default" {
-			Ok(Self::Default)
+			if input.peek(Token![=]) || input.peek(syn::token::Paren) {
+				parse_path_arg(input).map(|path| Self::Default(FieldDefault::Path(path)))
+			} else {
+				Ok(Self::Default(FieldDefault::Default))
+			}
+		} else if id == "default_seeded" {
+			parse_path_arg(input).map(|path| Self::Default(FieldDefault::Seeded(path)))
+		} else if id == "required" {
+			Ok(Self::Required)
 		} else if id == "with" {
-			let content;
-			let _ = syn::parenthesized!(content in input);
-			content.parse().map(Self::With)
+			parse_path_arg(input).map(Self::With)
+		} else if id == "with_unseeded" {
+			parse_path_arg(input).map(Self::WithUnseeded)
+		} else if id == "serialize_with" {
+			parse_path_arg(input).map(|path| Self::SerializeWith(FieldFn::Unseeded(path)))
+		} else if id == "serialize_with_seeded" {
+			parse_path_arg(input).map(|path| Self::SerializeWith(FieldFn::Seeded(path)))
+		} else if id == "deserialize_with" {
+			parse_path_arg(input).map(|path| Self::DeserializeWith(FieldFn::Unseeded(path)))
+		} else if id == "deserialize_with_seeded" {
+			parse_path_arg(input).map(|path| Self::DeserializeWith(FieldFn::Seeded(path)))
 		} else if id == "skip_serializing_if" {
-			let content;
-			let _ = syn::parenthesized!(content in input);
-			content.parse().map(Self::SkipSerializingIf)
+			parse_path_arg(input).map(Self::SkipSerializingIf)
+		} else if id == "skip_serializing_if_seeded" {
+			parse_path_arg(input).map(Self::SkipSerializingIfSeeded)
 		} else if id == "rename" {
-			let content;
-			let _ = syn::parenthesized!(content in input);
-			content.parse().map(Self::Rename)
+			parse_arg(input).map(Self::Rename)
+		} else if id == "flatten" {
+			Ok(Self::Flatten)
+		} else if id == "seed" {
+			parse_arg(input).map(Self::Seed)
+		} else if id == "getter" {
+			parse_path_arg(input).map(Self::Getter)
+		} else {
+			Err(syn::Error::new(id.span(), "unexpected ident"))
+		}
+	}
+}
+
+#[derive(Default)]
+pub struct VariantAttributes {
+	pub rename: Option<String>,
+	pub rename_all: RenameRule,
+	pub aliases: Vec<String>,
+
+	/// Skipped variants keep their declaration index, so that the indices of
+	/// the following variants are the same when serializing and
+	/// deserializing, as with `serde`.
+	pub skip: bool,
+	pub other: bool,
+}
+
+impl VariantAttributes {
+	pub fn parse_attributes(attrs: &[syn::Attribute]) -> Result<Self, Error> {
+		let mut result = Self::default();
+
+		for attr in attrs {
+			if attr.path().is_ident("seeded") {
+				match &attr.meta {
+					syn::Meta::List(list) => {
+						let a: Self = syn::parse2(list.tokens.clone())?;
+						result.merge_with(a)
+					}
+					_ => return Err(Error::ExpectedMetaList(attr.span())),
+				}
+			}
+		}
+
+		Ok(result)
+	}
+
+	pub fn merge_with(&mut self, other: Self) {
+		self.skip |= other.skip;
+		self.other |= other.other;
+		self.aliases.extend(other.aliases);
+		self.rename_all = other.rename_all.or(self.rename_all);
+
+		if let Some(name) = other.rename {
+			self.rename = Some(name)
+		}
+	}
+
+	pub fn name(&self, ident: &syn::Ident, rule: RenameRule) -> String {
+		match &self.rename {
+			Some(name) => name.clone(),
+			None => rule.apply_to_variant(&unraw(ident)),
+		}
+	}
+}
+
+impl syn::parse::Parse for VariantAttributes {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		let mut result = Self::default();
+
+		let attributes: Punctuated<VariantAttribute, Token![,]> =
+			Punctuated::parse_terminated(input)?;
+		for attr in attributes {
+			match attr {
+				VariantAttribute::Rename(name) => result.rename = Some(name.value()),
+				VariantAttribute::RenameAll(rule) => result.rename_all = rule,
+				VariantAttribute::Alias(name) => result.aliases.push(name.value()),
+				VariantAttribute::Skip => result.skip = true,
+				VariantAttribute::Other => result.other = true,
+			}
+		}
+
+		Ok(result)
+	}
+}
+
+pub enum VariantAttribute {
+	Rename(syn::LitStr),
+	RenameAll(RenameRule),
+	Alias(syn::LitStr),
+	Skip,
+	Other,
+}
+
+impl syn::parse::Parse for VariantAttribute {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		let id: syn::Ident = input.parse()?;
+		if id == "rename" {
+			parse_arg(input).map(Self::Rename)
+		} else if id == "rename_all" {
+			parse_rename_rule(input).map(Self::RenameAll)
+		} else if id == "alias" {
+			parse_arg(input).map(Self::Alias)
+		} else if id == "skip" {
+			Ok(Self::Skip)
+		} else if id == "other" {
+			Ok(Self::Other)
 		} else {
 			Err(syn::Error::new(id.span(), "unexpected ident"))
 		}
@@ -146,6 +428,18 @@ pub struct TypeAttributes {
 	pub de: Vec<SerializeAttributes>,
 	pub transparent: bool,
 	pub rename: Option<String>,
+	pub rename_all: RenameRule,
+	pub rename_all_fields: RenameRule,
+	pub deny_unknown_fields: bool,
+	pub tag: Option<syn::LitStr>,
+	pub content: Option<syn::LitStr>,
+	pub untagged: bool,
+	pub validate: Option<syn::Path>,
+	pub from: Option<syn::Type>,
+	pub try_from: Option<syn::Type>,
+	pub into: Option<syn::Type>,
+	pub remote: Option<syn::Path>,
+	pub crate_path: Option<syn::Path>,
 }
 
 impl TypeAttributes {
@@ -171,10 +465,55 @@ impl TypeAttributes {
 		self.ser.extend(other.ser);
 		self.de.extend(other.de);
 		self.transparent |= other.transparent;
+		self.deny_unknown_fields |= other.deny_unknown_fields;
+		self.untagged |= other.untagged;
+		self.rename_all = other.rename_all.or(self.rename_all);
+		self.rename_all_fields = other.rename_all_fields.or(self.rename_all_fields);
 
 		if let Some(name) = other.rename {
 			self.rename = Some(name)
 		}
+
+		if let Some(path) = other.validate {
+			self.validate = Some(path)
+		}
+
+		if let Some(tag) = other.tag {
+			self.tag = Some(tag)
+		}
+
+		if let Some(content) = other.content {
+			self.content = Some(content)
+		}
+
+		if let Some(ty) = other.from {
+			self.from = Some(ty)
+		}
+
+		if let Some(ty) = other.try_from {
+			self.try_from = Some(ty)
+		}
+
+		if let Some(ty) = other.into {
+			self.into = Some(ty)
+		}
+
+		if let Some(path) = other.remote {
+			self.remote = Some(path)
+		}
+
+		if let Some(path) = other.crate_path {
+			self.crate_path = Some(path)
+		}
+	}
+
+	/// Path of the serialized type, which is either the remote type or the
+	/// type itself.
+	pub fn value_path(&self, ident: &syn::Ident) -> syn::Path {
+		match &self.remote {
+			Some(path) => path.clone(),
+			None => ident.clone().into(),
+		}
 	}
 
 	pub fn name(&self, ident: &syn::Ident) -> String {
@@ -183,6 +522,93 @@ impl TypeAttributes {
 			None => ident.to_string(),
 		}
 	}
+
+	/// Returns the representation of the enum, as given by the `tag`,
+	/// `content` and `untagged` attributes.
+	pub fn enum_repr(&self) -> Result<EnumRepr, Error> {
+		if self.untagged {
+			return match self.tag.as_ref().or(self.content.as_ref()) {
+				Some(lit) => Err(Error::UntaggedWithTag(lit.span())),
+				None => Ok(EnumRepr::Untagged),
+			};
+		}
+
+		match (&self.tag, &self.content) {
+			(None, None) => Ok(EnumRepr::External),
+			(Some(tag), None) => Ok(EnumRepr::Internal { tag: tag.value() }),
+			(Some(tag), Some(content)) => Ok(EnumRepr::Adjacent {
+				tag: tag.value(),
+				content: content.value(),
+			}),
+			(None, Some(content)) => Err(Error::ContentWithoutTag(content.span())),
+		}
+	}
+
+	/// Returns the proxy type to deserialize from, as given by the `from` or
+	/// `try_from` attributes.
+	pub fn proxy_from(&self) -> Result<Option<FromProxy<'_>>, Error> {
+		match (&self.from, &self.try_from) {
+			(None, None) => Ok(None),
+			(Some(ty), None) => Ok(Some(FromProxy::From(ty))),
+			(None, Some(ty)) => Ok(Some(FromProxy::TryFrom(ty))),
+			(Some(_), Some(ty)) => Err(Error::FromAndTryFrom(ty.span())),
+		}
+	}
+
+	/// Wraps the generated code in an anonymous constant, where the
+	/// `_serde_seeded` and `_serde` paths refer to the `serde-seeded` and
+	/// `serde` crates.
+	///
+	/// The `serde-seeded` crate path can be overridden with the `crate`
+	/// attribute.
+	pub fn wrap_in_const(&self, tokens: TokenStream) -> TokenStream {
+		let crate_path = match &self.crate_path {
+			Some(path) => quote! { #path },
+			None => quote! { ::serde_seeded },
+		};
+
+		quote! {
+			const _: () = {
+				use #crate_path as _serde_seeded;
+				use _serde_seeded::private::serde as _serde;
+
+				#tokens
+			};
+		}
+	}
+
+	/// Makes sure no enum representation attribute is used.
+	pub fn reject_enum_repr(&self) -> Result<(), Error> {
+		match self.tag.as_ref().or(self.content.as_ref()) {
+			Some(lit) => Err(Error::UnexpectedTag(lit.span())),
+			None if self.untagged => Err(Error::UnexpectedTag(Span::call_site())),
+			None => Ok(()),
+		}
+	}
+}
+
+/// Enum representation.
+pub enum EnumRepr {
+	/// `{ "Variant": content }`, the default.
+	External,
+
+	/// `{ "tag": "Variant", ...fields }`.
+	Internal { tag: String },
+
+	/// `{ "tag": "Variant", "content": content }`.
+	Adjacent { tag: String, content: String },
+
+	/// `content`, trying each variant in order.
+	Untagged,
+}
+
+/// Proxy type a type is deserialized from.
+pub enum FromProxy<'a> {
+	/// Converted with `From`.
+	From(&'a syn::Type),
+
+	/// Converted with `TryFrom`, failing with the conversion error.
+	TryFrom(&'a syn::Type),
 }
 
 impl syn::parse::Parse for TypeAttributes {
@@ -195,13 +621,37 @@ impl syn::parse::Parse for TypeAttributes {
 				TypeAttribute::Ser(a) => {
 					result.ser.push(a);
 				}
+				TypeAttribute::SerMut(a) => result.ser.push(SerializeAttributes {
+					mode: SeedMode::Mut,
+					..a
+				}),
 				TypeAttribute::De(a) => result.de.push(a),
+				TypeAttribute::DeMut(a) => result.de.push(SerializeAttributes {
+					mode: SeedMode::Mut,
+					..a
+				}),
+				TypeAttribute::DeRef(a) => result.de.push(SerializeAttributes {
+					mode: SeedMode::Ref,
+					..a
+				}),
 				TypeAttribute::Serde(a) => {
 					result.ser.push(a.clone());
 					result.de.push(a);
 				}
 				TypeAttribute::Transparent => result.transparent = true,
 				TypeAttribute::Rename(name) => result.rename = Some(name.value()),
+				TypeAttribute::RenameAll(rule) => result.rename_all = rule,
+				TypeAttribute::RenameAllFields(rule) => result.rename_all_fields = rule,
+				TypeAttribute::DenyUnknownFields => result.deny_unknown_fields = true,
+				TypeAttribute::Tag(tag) => result.tag = Some(tag),
+				TypeAttribute::Content(content) => result.content = Some(content),
+				TypeAttribute::Untagged => result.untagged = true,
+				TypeAttribute::Validate(path) => result.validate = Some(path),
+				TypeAttribute::From(ty) => result.from = Some(ty),
+				TypeAttribute::TryFrom(ty) => result.try_from = Some(ty),
+				TypeAttribute::Into(ty) => result.into = Some(ty),
+				TypeAttribute::Remote(path) => result.remote = Some(path),
+				TypeAttribute::Crate(path) => result.crate_path = Some(path),
 			}
 		}
 
@@ -211,23 +661,51 @@ impl syn::parse::Parse for TypeAttributes {
 
 pub enum TypeAttribute {
 	Ser(SerializeAttributes),
+	SerMut(SerializeAttributes),
 	De(SerializeAttributes),
+	DeMut(SerializeAttributes),
+	DeRef(SerializeAttributes),
 	Serde(SerializeAttributes),
 	Transparent,
 	Rename(syn::LitStr),
+	RenameAll(RenameRule),
+	RenameAllFields(RenameRule),
+	DenyUnknownFields,
+	Tag(syn::LitStr),
+	Content(syn::LitStr),
+	Untagged,
+	Validate(syn::Path),
+	From(syn::Type),
+	TryFrom(syn::Type),
+	Into(syn::Type),
+	Remote(syn::Path),
+	Crate(syn::Path),
 }
 
 impl syn::parse::Parse for TypeAttribute {
 	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-		let id: syn::Ident = input.parse()?;
+		// `crate` is a keyword.
+		let id = syn::Ident::parse_any(input)?;
 		if id == "ser" {
 			let content;
 			let _ = syn::parenthesized!(content in input);
 			SerializeAttributes::parse(&content).map(Self::Ser)
+		} else if id == "ser_mut" {
+			let content;
+			let _ = syn::parenthesized!(content in input);
+			SerializeAttributes::parse(&content).map(Self::SerMut)
 		} else if id == "de" {
 			let content;
 			let _ = syn::parenthesized!(content in input);
 			SerializeAttributes::parse(&content).map(Self::De)
+		} else if id == "de_mut" {
+			let content;
+			let _ = syn::parenthesized!(content in input);
+			SerializeAttributes::parse(&content).map(Self::DeMut)
+		} else if id == "de_ref" {
+			let content;
+			let _ = syn::parenthesized!(content in input);
+			SerializeAttributes::parse(&content).map(Self::DeRef)
 		} else if id == "serde" {
 			let content;
 			let _ = syn::parenthesized!(content in input);
@@ -235,9 +713,31 @@ impl syn::parse::Parse for TypeAttribute {
 		} else if id == "transparent" {
 			Ok(Self::Transparent)
 		} else if id == "rename" {
-			let content;
-			let _ = syn::parenthesized!(content in input);
-			content.parse().map(Self::Rename)
+			parse_arg(input).map(Self::Rename)
+		} else if id == "rename_all" {
+			parse_rename_rule(input).map(Self::RenameAll)
+		} else if id == "rename_all_fields" {
+			parse_rename_rule(input).map(Self::RenameAllFields)
+		} else if id == "deny_unknown_fields" {
+			Ok(Self::DenyUnknownFields)
+		} else if id == "tag" {
+			parse_arg(input).map(Self::Tag)
+		} else if id == "content" {
+			parse_arg(input).map(Self::Content)
+		} else if id == "untagged" {
+			Ok(Self::Untagged)
+		} else if id == "validate" {
+			parse_path_arg(input).map(Self::Validate)
+		} else if id == "from" {
+			parse_path_arg(input).map(Self::From)
+		} else if id == "try_from" {
+			parse_path_arg(input).map(Self::TryFrom)
+		} else if id == "into" {
+			parse_path_arg(input).map(Self::Into)
+		} else if id == "remote" {
+			parse_path_arg(input).map(Self::Remote)
+		} else if id == "crate" {
+			parse_path_arg(input).map(Self::Crate)
 		} else {
 			Err(syn::Error::new(id.span(), "unexpected ident"))
 		}
@@ -250,9 +750,80 @@ pub struct SerializeAttributes {
 	pub params: Vec<syn::GenericParam>,
 	pub bounds: Vec<WherePredicate>,
 	pub override_bounds: Vec<WherePredicate>,
+
+	/// Components required from the seed, as given by
+	/// `seed(name: Component, ...)`.
+	pub components: Vec<SeedComponent>,
+
+	/// How the seed is passed.
+	pub mode: SeedMode,
+}
+
+/// Component required from the seed, bound to `name` in field seed
+/// expressions.
+#[derive(Clone)]
+pub struct SeedComponent {
+	pub name: syn::Ident,
+	pub ty: syn::Type,
+}
+
+impl syn::parse::Parse for SeedComponent {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		let name = input.parse()?;
+		input.parse::<Token![:]>()?;
+		let ty = input.parse()?;
+		Ok(Self { name, ty })
+	}
+}
+
+/// How the seed is passed to the (de)serialization functions.
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub enum SeedMode {
+	/// `&Q`, as given by `ser`, `de` or `serde`.
+	#[default]
+	Shared,
+
+	/// `&mut Q`, as given by `ser_mut` or `de_mut`.
+	Mut,
+
+	/// `&'seed Q`, borrowed by the deserialized value, as given by `de_ref`.
+	Ref,
 }
 
 impl SerializeAttributes {
+	/// Returns these attributes, made generic over the seed type `__Q` if
+	/// no seed type is given.
+	///
+	/// The given types are then required to support the seed type, and the
+	/// seed type to provide the required components.
+	pub fn or_generic_seed(
+		&self,
+		bounded_types: &[syn::Type],
+		bound: impl Fn(&syn::Type) -> TokenStream,
+	) -> Self {
+		let mut result = self.clone();
+
+		if result.seed.is_none() {
+			result.seed = Some(syn::parse_quote!(__Q));
+			result.params.push(syn::parse_quote!(__Q: ?Sized));
+			result.bounds.extend(
+				bounded_types
+					.iter()
+					.map(|ty| -> WherePredicate { syn::parse2(bound(ty)).unwrap() }),
+			);
+		}
+
+		let seed = result.seed.as_ref().unwrap();
+		result
+			.bounds
+			.extend(self.components.iter().map(|c| -> WherePredicate {
+				let ty = &c.ty;
+				syn::parse_quote!(#seed: _serde_seeded::context::Provide<#ty>)
+			}));
+
+		result
+	}
+
 	pub fn require_seed(&self) -> Result<&syn::Type, Error> {
 		self.seed
 			.as_ref()
@@ -272,6 +843,9 @@ impl syn::parse::Parse for SerializeAttributes {
 				SerializeAttribute::Seed(ty) => {
 					result.seed = Some(ty);
 				}
+				SerializeAttribute::Components(components) => {
+					result.components.extend(components);
+				}
 				SerializeAttribute::Params(params) => {
 					result.params.extend(params);
 				}
@@ -290,6 +864,7 @@ impl syn::parse::Parse for SerializeAttributes {
 
 pub enum SerializeAttribute {
 	Seed(syn::Type),
+	Components(Punctuated<SeedComponent, Token![,]>),
 	Params(Punctuated<syn::GenericParam, Token![,]>),
 	Bounds(Punctuated<WherePredicate, Token![,]>),
 	OverrideBounds(Punctuated<WherePredicate, Token![,]>),
@@ -301,7 +876,31 @@ impl syn::parse::Parse for SerializeAttribute {
 		if id == "seed" {
 			let content;
 			let _ = syn::parenthesized!(content in input);
-			let ty = syn::Type::parse(&content)?;
+
+			// `seed(name: Component, ...)` requires components from a seed
+			// of any type instead.
+			if content.peek(syn::Ident) && content.peek2(Token![:]) && !content.peek2(Token![::]) {
+				let components = Punctuated::parse_terminated(&content)?;
+				return Ok(Self::Components(components));
+			}
+
+			let mut ty = syn::Type::parse(&content)?;
+
+			// Trait object seeds are made explicitly `'static`, as they are
+			// in the trait implementations, so that references to the seed
+			// in the generated code do not default to a shorter lifetime.
+			if let syn::Type::TraitObject(object) = &mut ty {
+				if !object
+					.bounds
+					.iter()
+					.any(|b| matches!(b, syn::TypeParamBound::Lifetime(_)))
+				{
+					object.bounds.push(syn::parse_quote!('static));
+				}
+
+				ty = syn::parse_quote!((#object));
+			}
+
 			Ok(Self::Seed(ty))
 		} else if id == "params" {
 			let content;
@@ -325,3 +924,45 @@ impl syn::parse::Parse for SerializeAttribute {
 		}
 	}
 }
+
+/// Parses the value of an attribute, either of the form `name(value)` or
+/// `name = value`.
+fn parse_arg<T: syn::parse::Parse>(input: syn::parse::ParseStream) -> syn::Result<T> {
+	if input.peek(Token![=]) {
+		input.parse::<Token![=]>()?;
+		input.parse()
+	} else {
+		let content;
+		let _ = syn::parenthesized!(content in input);
+		content.parse()
+	}
+}
+
+/// Same as [`parse_arg`], but also accepts the value to be given as a string
+/// literal, as in `name = "value"`.
+fn parse_path_arg<T: syn::parse::Parse>(input: syn::parse::ParseStream) -> syn::Result<T> {
+	if input.peek(Token![=]) && input.peek2(syn::LitStr) {
+		input.parse::<Token![=]>()?;
+		input.parse::<syn::LitStr>()?.parse()
+	} else {
+		parse_arg(input)
+	}
+}
+
+/// Parses a case convention given to `rename_all` or `rename_all_fields`.
+fn parse_rename_rule(input: syn::parse::ParseStream) -> syn::Result<RenameRule> {
+	let lit: syn::LitStr = parse_arg(input)?;
+	lit.value()
+		.parse()
+		.map_err(|e| syn::Error::new(lit.span(), e))
+}
+
+/// Returns the name of the given identifier, without the `r#` prefix.
+fn unraw(ident: &syn::Ident) -> String {
+	let name = ident.to_string();
+
+	match name.strip_prefix("r#") {
+		Some(suffix) => suffix.to_owned(),
+		None => name,
+	}
+}
@@ -0,0 +1,152 @@
+//! Case conventions used by the `rename_all` and `rename_all_fields`
+//! attributes.
+use std::{fmt, str::FromStr};
+
+/// Case convention to apply to the names of fields or variants.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RenameRule {
+	/// Leave the names untouched.
+	#[default]
+	None,
+
+	/// `lowercase`.
+	LowerCase,
+
+	/// `UPPERCASE`.
+	UpperCase,
+
+	/// `PascalCase`.
+	PascalCase,
+
+	/// `camelCase`.
+	CamelCase,
+
+	/// `snake_case`.
+	SnakeCase,
+
+	/// `SCREAMING_SNAKE_CASE`.
+	ScreamingSnakeCase,
+
+	/// `kebab-case`.
+	KebabCase,
+
+	/// `SCREAMING-KEBAB-CASE`.
+	ScreamingKebabCase,
+}
+
+const RULES: [(&str, RenameRule); 8] = [
+	("lowercase", RenameRule::LowerCase),
+	("UPPERCASE", RenameRule::UpperCase),
+	("PascalCase", RenameRule::PascalCase),
+	("camelCase", RenameRule::CamelCase),
+	("snake_case", RenameRule::SnakeCase),
+	("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnakeCase),
+	("kebab-case", RenameRule::KebabCase),
+	("SCREAMING-KEBAB-CASE", RenameRule::ScreamingKebabCase),
+];
+
+impl FromStr for RenameRule {
+	type Err = UnknownRenameRule;
+
+	fn from_str(name: &str) -> Result<Self, UnknownRenameRule> {
+		RULES
+			.iter()
+			.find_map(|(n, rule)| (*n == name).then_some(*rule))
+			.ok_or_else(|| UnknownRenameRule(name.to_owned()))
+	}
+}
+
+impl RenameRule {
+	/// Returns the first rule that is not `None`.
+	pub fn or(self, other: Self) -> Self {
+		match self {
+			Self::None => other,
+			rule => rule,
+		}
+	}
+
+	/// Applies the rule to a variant name, assumed to be in `PascalCase`.
+	pub fn apply_to_variant(self, variant: &str) -> String {
+		match self {
+			Self::None | Self::PascalCase => variant.to_owned(),
+			Self::LowerCase => variant.to_ascii_lowercase(),
+			Self::UpperCase => variant.to_ascii_uppercase(),
+			Self::CamelCase => {
+				let mut chars = variant.chars();
+				match chars.next() {
+					Some(c) => c.to_ascii_lowercase().to_string() + chars.as_str(),
+					None => String::new(),
+				}
+			}
+			Self::SnakeCase => {
+				let mut snake = String::new();
+				for (i, c) in variant.char_indices() {
+					if i > 0 && c.is_uppercase() {
+						snake.push('_');
+					}
+					snake.push(c.to_ascii_lowercase());
+				}
+				snake
+			}
+			Self::ScreamingSnakeCase => Self::SnakeCase
+				.apply_to_variant(variant)
+				.to_ascii_uppercase(),
+			Self::KebabCase => Self::SnakeCase.apply_to_variant(variant).replace('_', "-"),
+			Self::ScreamingKebabCase => Self::ScreamingSnakeCase
+				.apply_to_variant(variant)
+				.replace('_', "-"),
+		}
+	}
+
+	/// Applies the rule to a field name, assumed to be in `snake_case`.
+	pub fn apply_to_field(self, field: &str) -> String {
+		match self {
+			Self::None | Self::LowerCase | Self::SnakeCase => field.to_owned(),
+			Self::UpperCase | Self::ScreamingSnakeCase => field.to_ascii_uppercase(),
+			Self::PascalCase => {
+				let mut pascal = String::new();
+				let mut capitalize = true;
+				for c in field.chars() {
+					if c == '_' {
+						capitalize = true;
+					} else if capitalize {
+						pascal.push(c.to_ascii_uppercase());
+						capitalize = false;
+					} else {
+						pascal.push(c);
+					}
+				}
+				pascal
+			}
+			Self::CamelCase => {
+				let pascal = Self::PascalCase.apply_to_field(field);
+				let mut chars = pascal.chars();
+				match chars.next() {
+					Some(c) => c.to_ascii_lowercase().to_string() + chars.as_str(),
+					None => String::new(),
+				}
+			}
+			Self::KebabCase => field.replace('_', "-"),
+			Self::ScreamingKebabCase => field.to_ascii_uppercase().replace('_', "-"),
+		}
+	}
+}
+
+#[derive(Debug)]
+pub struct UnknownRenameRule(String);
+
+impl fmt::Display for UnknownRenameRule {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "unknown rename rule `{}`, expected one of ", self.0)?;
+
+		for (i, (name, _)) in RULES.iter().enumerate() {
+			if i > 0 {
+				write!(f, ", ")?;
+			}
+
+			write!(f, "`{name}`")?;
+		}
+
+		Ok(())
+	}
+}
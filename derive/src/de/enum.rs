@@ -4,8 +4,8 @@ use quote::quote;
 use crate::{de::fields, SerializedFields};
 
 use super::{
-	attributes::{SerializeAttributes, TypeAttributes},
-	split_visitor_generics, Error,
+	attributes::{EnumRepr, SeedMode, SerializeAttributes, TypeAttributes, VariantAttributes},
+	seed_mutability, split_visitor_generics, Error,
 };
 
 pub fn derive(
@@ -16,18 +16,65 @@ pub fn derive(
 	e: &syn::DataEnum,
 ) -> Result<TokenStream, Error> {
 	let name = attrs.name(ident);
-	let count = e.variants.len();
 
-	let variants_idents = e.variants.iter().map(|v| &v.ident);
-	let variants_names = e.variants.iter().map(|v| v.ident.to_string());
-
-	let variants_cases: Vec<_> = e
+	let variants = e
 		.variants
 		.iter()
-		.map(|v| {
+		.enumerate()
+		.map(|(i, v)| {
+			let variant_attrs = VariantAttributes::parse_attributes(&v.attrs)?;
+			Ok((i, v, variant_attrs))
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
+
+	let variants: Vec<_> = variants
+		.into_iter()
+		.filter(|(_, _, variant_attrs)| !variant_attrs.skip)
+		.collect();
+
+	let repr = attrs.enum_repr()?;
+	let internally_tagged = matches!(repr, EnumRepr::Internal { .. });
+
+	if matches!(repr, EnumRepr::Untagged) && de.mode == SeedMode::Mut {
+		return Err(Error::UntaggedMutableSeed(ident.span()));
+	}
+
+	let mut other = None;
+	for (_, v, variant_attrs) in &variants {
+		if internally_tagged {
+			if let syn::Fields::Unnamed(fields) = &v.fields {
+				if fields.unnamed.len() != 1 {
+					return Err(Error::InternallyTaggedTuple(v.ident.span()));
+				}
+			}
+		}
+
+		if variant_attrs.other {
+			if !matches!(v.fields, syn::Fields::Unit) {
+				return Err(Error::OtherNotUnit(v.ident.span()));
+			}
+
+			if other.replace(&v.ident).is_some() {
+				return Err(Error::MultipleOther(v.ident.span()));
+			}
+		}
+	}
+
+	let count = variants.len();
+	let variants_idents = variants.iter().map(|(_, v, _)| &v.ident);
+	let variants_names = variants
+		.iter()
+		.map(|(_, v, variant_attrs)| variant_attrs.name(&v.ident, attrs.rename_all));
+
+	let variants_cases: Vec<_> = variants
+		.iter()
+		.map(|(_, v, variant_attrs)| {
 			let variant_ident = &v.ident;
 
-			let fields = SerializedFields::new(&v.fields)?;
+			let fields = SerializedFields::new(
+				&v.fields,
+				variant_attrs.rename_all.or(attrs.rename_all_fields),
+			)?;
 			let de_fields =
 				fields::derive(ident, generics, attrs, de, Some(variant_ident), &fields)?;
 
@@ -39,8 +86,10 @@ pub fn derive(
 		})
 		.collect::<Result<_, Error>>()?;
 
-	let cases_u64 = e.variants.iter().enumerate().map(|(i, v)| {
-		let i = i as u64;
+	// Indices are declaration indices, skipped variants included, matching
+	// the serialization side.
+	let cases_u64 = variants.iter().map(|(i, v, _)| {
+		let i = *i as u64;
 		let variant_ident = &v.ident;
 
 		quote! {
@@ -48,118 +97,325 @@ pub fn derive(
 		}
 	});
 
-	let cases_str = e.variants.iter().map(|v| {
-		let variant_name = v.ident.to_string();
+	let cases_str = variants.iter().map(|(_, v, variant_attrs)| {
+		let variant_names = std::iter::once(variant_attrs.name(&v.ident, attrs.rename_all))
+			.chain(variant_attrs.aliases.iter().cloned());
 		let variant_ident = &v.ident;
 
 		quote! {
-			#variant_name => Ok(Discriminant::#variant_ident)
+			#(#variant_names)|* => Ok(Discriminant::#variant_ident)
 		}
 	});
 
-	let cases_bytes = e.variants.iter().map(|v| {
-		let variant_name = v.ident.to_string();
-		let variant_bytes = syn::LitByteStr::new(variant_name.as_bytes(), v.ident.span());
+	let cases_bytes = variants.iter().map(|(_, v, variant_attrs)| {
+		let variant_bytes = std::iter::once(variant_attrs.name(&v.ident, attrs.rename_all))
+			.chain(variant_attrs.aliases.iter().cloned())
+			.map(|name| syn::LitByteStr::new(name.as_bytes(), v.ident.span()));
 		let variant_ident = &v.ident;
 
 		quote! {
-			#variant_bytes => Ok(Discriminant::#variant_ident)
+			#(#variant_bytes)|* => Ok(Discriminant::#variant_ident)
 		}
 	});
 
+	let (unknown_index, unknown_str, unknown_bytes) = match other {
+		Some(other) => (
+			quote! { Ok(Discriminant::#other) },
+			quote! { Ok(Discriminant::#other) },
+			quote! { Ok(Discriminant::#other) },
+		),
+		None => (
+			quote! {
+				Err(_serde::de::Error::invalid_value(_serde::de::Unexpected::Unsigned(v), &"variant index"))
+			},
+			quote! {
+				Err(_serde::de::Error::unknown_variant(v, &VARIANTS))
+			},
+			quote! {
+				// See https://github.com/serde-rs/serde/blob/e3eaa6a3dd6edd701476097182313cdbd73da78c/serde/src/de/impls.rs#L1664C33-L1667C34
+				match ::core::str::from_utf8(v) {
+					Ok(v) => Err(_serde::de::Error::unknown_variant(v, &VARIANTS)),
+					Err(_) => Err(_serde::de::Error::invalid_value(_serde::de::Unexpected::Bytes(v), &self))
+				}
+			},
+		),
+	};
+
 	let seed_ty = de.require_seed()?;
+	let mutability = seed_mutability(de);
 	let (def_generics, impl_generics, ty_generics, where_clause, value_generics) =
 		split_visitor_generics(generics, de);
 
-	Ok(quote! {
-		const VARIANTS: [&str; #count] = [
-			#(#variants_names),*
-		];
-
-		struct Visitor #def_generics {
-			seed: &'seed #seed_ty,
-			p: ::core::marker::PhantomData<#ident #value_generics>
-		}
+	let (visit, entry) = match repr {
+		EnumRepr::External => (
+			quote! {
+				fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+				where
+					A: _serde::de::EnumAccess<'de>
+				{
+					let (discriminant, variant) = data.variant::<Discriminant>()?;
+					self.visit_variant(discriminant, variant)
+				}
+			},
+			quote! {
+				deserializer.deserialize_enum(#name, &VARIANTS, Visitor {
+					seed,
+					p: ::core::marker::PhantomData::<#ident #value_generics>
+				})
+			},
+		),
+		EnumRepr::Internal { tag } => {
+			let expecting = format!("internally tagged enum {name}");
 
-		impl #impl_generics ::serde::de::Visitor<'de> for Visitor #ty_generics #where_clause {
-			type Value = #ident #value_generics;
+			(
+				quote! {},
+				quote! {
+					let (discriminant, content) = deserializer.deserialize_any(
+						_serde_seeded::private::de::TaggedContentVisitor::<Discriminant>::new(#tag, #expecting)
+					)?;
 
-			fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-				write!(formatter, "enum value")
-			}
+					Visitor {
+						seed,
+						p: ::core::marker::PhantomData::<#ident #value_generics>
+					}.visit_variant(
+						discriminant,
+						_serde_seeded::private::de::ContentVariant::<D::Error>::internally_tagged(content)
+					)
+				},
+			)
+		}
+		EnumRepr::Untagged => {
+			let attempts = variants.iter().map(|(_, v, variant_attrs)| {
+				let variant_ident = &v.ident;
+				let variant_name = variant_attrs.name(variant_ident, attrs.rename_all);
 
-			fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
-			where
-				A: ::serde::de::EnumAccess<'de>
-			{
-				enum Discriminant {
-					#(#variants_idents),*
+				quote! {
+					match (Visitor {
+						seed,
+						p: ::core::marker::PhantomData::<#ident #value_generics>
+					}).visit_variant(
+						Discriminant::#variant_ident,
+						_serde_seeded::private::de::ContentVariant::<D::Error>::new(content.clone())
+					) {
+						Ok(value) => return Ok(value),
+						Err(e) => errors.push(#variant_name, e)
+					}
 				}
+			});
 
-				struct DiscriminantVisitor;
+			(
+				quote! {},
+				quote! {
+					let content: _serde_seeded::private::de::Content =
+						_serde::Deserialize::deserialize(deserializer)?;
 
-				impl<'de> ::serde::de::Visitor<'de> for DiscriminantVisitor {
-					type Value = Discriminant;
+					let mut errors = _serde_seeded::private::de::UntaggedErrors::new(#name);
 
-					fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-						write!(formatter, "variant identifier")
-					}
+					#(#attempts)*
+
+					Err(errors.into_error())
+				},
+			)
+		}
+		EnumRepr::Adjacent { tag, content } => {
+			let field_seed = if attrs.deny_unknown_fields {
+				quote! {
+					TagOrContentFieldSeed::new(#tag, #content).deny_unknown_fields(&[#tag, #content])
+				}
+			} else {
+				quote! {
+					TagOrContentFieldSeed::new(#tag, #content)
+				}
+			};
 
-					fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+			(
+				quote! {
+					fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
 					where
-						E: ::serde::de::Error
+						A: _serde::de::MapAccess<'de>
 					{
-						match v {
-							#(#cases_u64,)*
-							_ => Err(::serde::de::Error::invalid_value(::serde::de::Unexpected::Unsigned(v), &"variant index"))
+						use _serde_seeded::private::de::{Content, ContentVariant, TagOrContentField, TagOrContentFieldSeed};
+
+						let mut discriminant = None;
+						let mut value = None;
+						let mut content = None;
+
+						while let Some(key) = map.next_key_seed(#field_seed)? {
+							match key {
+								TagOrContentField::Tag => {
+									if discriminant.is_some() {
+										return Err(_serde::de::Error::duplicate_field(#tag))
+									}
+
+									discriminant = Some(map.next_value::<Discriminant>()?)
+								}
+								TagOrContentField::Content => {
+									if value.is_some() || content.is_some() {
+										return Err(_serde::de::Error::duplicate_field(#content))
+									}
+
+									match discriminant {
+										Some(discriminant) => {
+											value = Some(map.next_value_seed(VariantSeed {
+												seed: self.seed,
+												discriminant,
+												p: ::core::marker::PhantomData::<#ident #value_generics>
+											})?)
+										}
+										None => content = Some(map.next_value::<Content>()?)
+									}
+								}
+								TagOrContentField::Other => {
+									map.next_value::<_serde::de::IgnoredAny>()?;
+								}
+							}
 						}
-					}
 
-					fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
-					where
-						E: ::serde::de::Error
-					{
-						match v {
-							#(#cases_str,)*
-							_ => Err(::serde::de::Error::unknown_variant(v, &VARIANTS))
+						match discriminant {
+							Some(discriminant) => match value {
+								Some(value) => Ok(value),
+								None => self.visit_variant(
+									discriminant,
+									ContentVariant::<A::Error>::new(content.unwrap_or(Content::Unit))
+								)
+							},
+							None => Err(_serde::de::Error::missing_field(#tag))
 						}
 					}
 
-					fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+					fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
 					where
-						E: ::serde::de::Error
+						A: _serde::de::SeqAccess<'de>
 					{
-						match v {
-							#(#cases_bytes,)*
-							// See https://github.com/serde-rs/serde/blob/e3eaa6a3dd6edd701476097182313cdbd73da78c/serde/src/de/impls.rs#L1664C33-L1667C34
-							_ => match str::from_utf8(v) {
-								Ok(v) => Err(::serde::de::Error::unknown_variant(v, &VARIANTS)),
-								Err(_) => Err(::serde::de::Error::invalid_value(::serde::de::Unexpected::Bytes(v), &self))
-							}
+						let discriminant = seq.next_element::<Discriminant>()?
+							.ok_or_else(|| _serde::de::Error::invalid_length(0, &self))?;
+
+						seq.next_element_seed(VariantSeed {
+							seed: self.seed,
+							discriminant,
+							p: ::core::marker::PhantomData::<#ident #value_generics>
+						})?.ok_or_else(|| _serde::de::Error::invalid_length(1, &self))
+					}
+				},
+				quote! {
+					struct VariantSeed #def_generics {
+						seed: &'seed #mutability #seed_ty,
+						discriminant: Discriminant,
+						p: ::core::marker::PhantomData<#ident #value_generics>
+					}
+
+					impl #impl_generics _serde::de::DeserializeSeed<'de> for VariantSeed #ty_generics #where_clause {
+						type Value = #ident #value_generics;
+
+						fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+						where
+							D: _serde::Deserializer<'de>
+						{
+							Visitor {
+								seed: self.seed,
+								p: ::core::marker::PhantomData::<#ident #value_generics>
+							}.visit_variant(
+								self.discriminant,
+								_serde_seeded::private::de::DeserializerVariant::new(deserializer, #name)
+							)
 						}
 					}
+
+					deserializer.deserialize_struct(#name, &[#tag, #content], Visitor {
+						seed,
+						p: ::core::marker::PhantomData::<#ident #value_generics>
+					})
+				},
+			)
+		}
+	};
+
+	Ok(quote! {
+		const VARIANTS: [&str; #count] = [
+			#(#variants_names),*
+		];
+
+		#[derive(Clone, Copy)]
+		enum Discriminant {
+			#(#variants_idents),*
+		}
+
+		struct DiscriminantVisitor;
+
+		impl<'de> _serde::de::Visitor<'de> for DiscriminantVisitor {
+			type Value = Discriminant;
+
+			fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				write!(formatter, "variant identifier")
+			}
+
+			fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+			where
+				E: _serde::de::Error
+			{
+				match v {
+					#(#cases_u64,)*
+					_ => #unknown_index
 				}
+			}
 
-				impl<'de> ::serde::de::Deserialize<'de> for Discriminant {
-					fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-					where
-						D: ::serde::Deserializer<'de>
-					{
-						deserializer.deserialize_identifier(DiscriminantVisitor)
-					}
+			fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+			where
+				E: _serde::de::Error
+			{
+				match v {
+					#(#cases_str,)*
+					_ => #unknown_str
 				}
+			}
+
+			fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+			where
+				E: _serde::de::Error
+			{
+				match v {
+					#(#cases_bytes,)*
+					_ => #unknown_bytes
+				}
+			}
+		}
 
-				let (discriminant, variant) = data.variant::<Discriminant>()?;
+		impl<'de> _serde::de::Deserialize<'de> for Discriminant {
+			fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+			where
+				D: _serde::Deserializer<'de>
+			{
+				deserializer.deserialize_identifier(DiscriminantVisitor)
+			}
+		}
+
+		struct Visitor #def_generics {
+			seed: &'seed #mutability #seed_ty,
+			p: ::core::marker::PhantomData<#ident #value_generics>
+		}
 
+		impl #impl_generics Visitor #ty_generics #where_clause {
+			/// Deserializes the content of the given variant.
+			fn visit_variant<A>(self, discriminant: Discriminant, variant: A) -> Result<#ident #value_generics, A::Error>
+			where
+				A: _serde::de::VariantAccess<'de>
+			{
 				match discriminant {
 					#(#variants_cases),*
 				}
 			}
 		}
 
-		deserializer.deserialize_enum(#name, &VARIANTS, Visitor {
-			seed,
-			p: ::core::marker::PhantomData::<#ident #value_generics>
-		})
+		impl #impl_generics _serde::de::Visitor<'de> for Visitor #ty_generics #where_clause {
+			type Value = #ident #value_generics;
+
+			fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				write!(formatter, "enum value")
+			}
+
+			#visit
+		}
+
+		#entry
 	})
 }
@@ -25,7 +25,7 @@ pub fn derive(
 	match fields {
 		SerializedFields::Unit => match variant_ident {
 			Some(variant_ident) => Ok(quote! {
-				::serde::de::VariantAccess::unit_variant(variant)?;
+				_serde::de::VariantAccess::unit_variant(variant)?;
 				Ok(#ident :: #variant_ident)
 			}),
 			None => {
@@ -37,14 +37,14 @@ pub fn derive(
 				Ok(quote! {
 					struct UnitVisitor #impl_generics (::core::marker::PhantomData<#ident #ty_generics>);
 
-					impl #visitor_impl_generics ::serde::de::Visitor<'de> for UnitVisitor #ty_generics #where_clause {
+					impl #visitor_impl_generics _serde::de::Visitor<'de> for UnitVisitor #ty_generics #where_clause {
 						type Value = #ident #ty_generics;
 
 						fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
 							write!(formatter, "unit")
 						}
 
-						fn visit_unit<E>(self) -> Result<Self::Value, E> where E: ::serde::de::Error {
+						fn visit_unit<E>(self) -> Result<Self::Value, E> where E: _serde::de::Error {
 							Ok(#ident)
 						}
 					}
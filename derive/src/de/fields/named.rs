@@ -2,8 +2,8 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
 use crate::{
-	attributes::{SerializeAttributes, TypeAttributes},
-	de::{deserialize_seed, split_visitor_generics, Error},
+	attributes::{FieldDefault, SerializeAttributes, TypeAttributes},
+	de::{deserialize_seed, seed_mutability, split_visitor_generics, Error},
 	utils::TryFilterMapExt,
 	SerializedNamedField,
 };
@@ -23,22 +23,31 @@ pub fn derive(
 		}
 	});
 
-	let count = fields.iter().filter(|f| !f.attrs.skip).count();
-
-	let fields_names = fields
+	let flatten = fields
 		.iter()
-		.filter_map(|f| if f.attrs.skip { None } else { Some(f.name()) });
+		.any(|f| !f.attrs.skip_deserializing && f.attrs.flatten);
+
+	if flatten && attrs.deny_unknown_fields {
+		return Err(Error::FlattenDenyUnknownFields(ident.span()));
+	}
+
+	// Fields deserialized from their own entry.
+	let is_entry = |f: &&SerializedNamedField| !f.attrs.skip_deserializing && !f.attrs.flatten;
+
+	let count = fields.iter().filter(is_entry).count();
+
+	let fields_names = fields.iter().filter(is_entry).map(|f| f.name());
 
 	let fields_variants = fields.iter().enumerate().filter_map(|(i, f)| {
-		if f.attrs.skip {
-			None
-		} else {
+		if is_entry(&f) {
 			Some(format_ident!("Field{i}"))
+		} else {
+			None
 		}
 	});
 
 	let define_fields = fields.iter().filter_map(|f| {
-		if f.attrs.skip {
+		if !is_entry(&f) {
 			None
 		} else {
 			let field_id = &f.id;
@@ -52,7 +61,7 @@ pub fn derive(
 		.iter()
 		.enumerate()
 		.try_filter_map(|(i, f)| {
-			if f.attrs.skip {
+			if !is_entry(&f) {
 				Ok(None)
 			} else {
 				let i = i as u64;
@@ -68,7 +77,7 @@ pub fn derive(
 		.iter()
 		.enumerate()
 		.try_filter_map(|(i, f)| {
-			if f.attrs.skip {
+			if !is_entry(&f) {
 				Ok(None)
 			} else {
 				let name = f.name();
@@ -84,7 +93,7 @@ pub fn derive(
 		.iter()
 		.enumerate()
 		.try_filter_map(|(i, f)| {
-			if f.attrs.skip {
+			if !is_entry(&f) {
 				Ok(None)
 			} else {
 				let name = f.name();
@@ -101,7 +110,7 @@ pub fn derive(
 		.iter()
 		.enumerate()
 		.try_filter_map(|(i, f)| {
-			if f.attrs.skip {
+			if !is_entry(&f) {
 				Ok(None)
 			} else {
 				let field_id = &f.id;
@@ -120,17 +129,80 @@ pub fn derive(
 		let field_id = &f.id;
 		let field_name = f.name();
 
-		if f.attrs.skip {
+		if f.attrs.flatten && !f.attrs.skip_deserializing {
+			quote! {
+				#field_id
+			}
+		} else if f.attrs.skip_deserializing {
+			let default = f.attrs.default_value(quote! { self.seed });
 			quote! {
-				#field_id: ::core::default::Default::default()
+				#field_id: #default
 			}
-		} else if f.attrs.default {
+		} else if let Some(FieldDefault::Default) = f.attrs.default {
 			quote! {
 				#field_id: #field_id.unwrap_or_default()
 			}
+		} else if f.attrs.default.is_some() {
+			let default = f.attrs.default_value(quote! { self.seed });
+			quote! {
+				#field_id: match #field_id {
+					Some(value) => value,
+					None => #default
+				}
+			}
+		} else if f.is_optional() {
+			quote! {
+				#field_id: #field_id.unwrap_or(None)
+			}
 		} else {
 			quote! {
-				#field_id: #field_id.ok_or_else(|| serde::de::Error::missing_field(#field_name))?
+				#field_id: #field_id.ok_or_else(|| _serde::de::Error::missing_field(#field_name))?
+			}
+		}
+	});
+
+	let expected_len = match variant_ident {
+		Some(variant_ident) => {
+			format!("struct variant {name}::{variant_ident} with {count} elements")
+		}
+		None => format!("struct {name} with {count} elements"),
+	};
+
+	let seq_fields = fields
+		.iter()
+		.filter(|f| !f.attrs.skip_deserializing)
+		.enumerate()
+		.map(|(i, f)| {
+			let field_id = &f.id;
+			let seed = deserialize_seed(ident, generics, de, &f.attrs, &f.ty)?;
+			let missing = if f.attrs.default.is_some() {
+				f.attrs.default_value(quote! { self.seed })
+			} else {
+				quote! {
+					return Err(_serde::de::Error::invalid_length(#i, &#expected_len))
+				}
+			};
+
+			Ok(quote! {
+				let #field_id = match seq__.next_element_seed(#seed)? {
+					Some(value) => value,
+					None => #missing
+				};
+			})
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
+
+	let init_seq_fields = fields.iter().map(|f| {
+		let field_id = &f.id;
+
+		if f.attrs.skip_deserializing {
+			let default = f.attrs.default_value(quote! { self.seed });
+			quote! {
+				#field_id: #default
+			}
+		} else {
+			quote! {
+				#field_id
 			}
 		}
 	});
@@ -141,11 +213,18 @@ pub fn derive(
 				#(#fields_names),*
 			];
 
-			::serde::de::VariantAccess::struct_variant(variant, &FIELDS, StructVisitor {
+			_serde::de::VariantAccess::struct_variant(variant, &FIELDS, StructVisitor {
 				seed: self.seed,
 				t: ::core::marker::PhantomData
 			})
 		}
+	} else if flatten {
+		quote! {
+			deserializer.deserialize_map(StructVisitor {
+				seed,
+				t: ::core::marker::PhantomData
+			})
+		}
 	} else {
 		quote! {
 			const FIELDS: [&str; #count] = [
@@ -159,39 +238,138 @@ pub fn derive(
 		}
 	};
 
+	let (ignore_variant, ignore_case, unknown_index, unknown_str, unknown_bytes) =
+		if attrs.deny_unknown_fields {
+			(
+				None,
+				None,
+				quote! {
+					Err(_serde::de::Error::invalid_value(_serde::de::Unexpected::Unsigned(v), &"field index"))
+				},
+				quote! {
+					Err(_serde::de::Error::unknown_field(v, &FIELDS))
+				},
+				quote! {
+					// See https://github.com/serde-rs/serde/blob/e3eaa6a3dd6edd701476097182313cdbd73da78c/serde/src/de/impls.rs#L1664C33-L1667C34
+					match ::core::str::from_utf8(v) {
+						Ok(v) => Err(_serde::de::Error::unknown_field(v, &FIELDS)),
+						Err(_) => Err(_serde::de::Error::invalid_value(_serde::de::Unexpected::Bytes(v), &self))
+					}
+				},
+			)
+		} else {
+			(
+				Some(quote! { Ignore__ }),
+				Some(quote! {
+					Field__::Ignore__ => {
+						map__.next_value::<_serde::de::IgnoredAny>()?;
+					}
+				}),
+				quote! { Ok(Field__::Ignore__) },
+				quote! { Ok(Field__::Ignore__) },
+				quote! { Ok(Field__::Ignore__) },
+			)
+		};
+
+	let read_entries = if flatten {
+		let flatten_fields = fields
+			.iter()
+			.filter(|f| !f.attrs.skip_deserializing && f.attrs.flatten)
+			.map(|f| {
+				let field_id = &f.id;
+				let seed = deserialize_seed(ident, generics, de, &f.attrs, &f.ty)?;
+				Ok(quote! {
+					let #field_id = _serde::de::DeserializeSeed::deserialize(
+						#seed,
+						_serde_seeded::private::de::FlatMapDeserializer::<A::Error>::new(&mut collect__)
+					)?;
+				})
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		// Unknown entries are collected for the flattened fields.
+		quote! {
+			let mut collect__ = _serde_seeded::private::de::FlatEntries::new();
+
+			while let Some(key) = map__.next_key::<_serde_seeded::private::de::Content>()? {
+				let field = _serde::Deserialize::deserialize(
+					_serde_seeded::private::de::ContentDeserializer::<A::Error>::new(key.clone())
+				)?;
+
+				match field {
+					#(#cases,)*
+					Field__::Ignore__ => {
+						collect__.push(Some((key, map__.next_value()?)))
+					}
+				}
+			}
+
+			#(#flatten_fields)*
+		}
+	} else {
+		quote! {
+			while let Some(field) = map__.next_key()? {
+				match field {
+					#(#cases,)*
+					#ignore_case
+				}
+			}
+		}
+	};
+
+	// Flattened fields cannot be read from a sequence.
+	let visit_seq = (!flatten).then(|| {
+		quote! {
+			fn visit_seq<A>(self, mut seq__: A) -> Result<Self::Value, A::Error>
+			where
+				A: _serde::de::SeqAccess<'de>
+			{
+				#(#seq_fields)*
+
+				Ok(#ident #variant_ext {
+					#(#init_seq_fields),*
+				})
+			}
+		}
+	});
+
 	let seed_ty = de.require_seed()?;
+	let mutability = seed_mutability(de);
 	let (def_generics, impl_generics, ty_generics, where_clause, value_generics) =
 		split_visitor_generics(generics, de);
 
 	Ok(quote! {
 		struct StructVisitor #def_generics {
-			seed: &'seed #seed_ty,
+			seed: &'seed #mutability #seed_ty,
 			t: ::core::marker::PhantomData<#ident #value_generics>
 		}
 
-		impl #impl_generics ::serde::de::Visitor<'de> for StructVisitor #ty_generics #where_clause {
+		impl #impl_generics _serde::de::Visitor<'de> for StructVisitor #ty_generics #where_clause {
 			type Value = #ident #value_generics;
 
 			fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
 				write!(formatter, "a struct")
 			}
 
+			#visit_seq
+
 			fn visit_map<A>(self, mut map__: A) -> Result<Self::Value, A::Error>
 			where
-				A: ::serde::de::MapAccess<'de>
+				A: _serde::de::MapAccess<'de>
 			{
 				enum Field__ {
-					#(#fields_variants),*
+					#(#fields_variants,)*
+					#ignore_variant
 				}
 
-				impl<'de> ::serde::Deserialize<'de> for Field__ {
+				impl<'de> _serde::Deserialize<'de> for Field__ {
 					fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 					where
-						D: ::serde::de::Deserializer<'de>
+						D: _serde::de::Deserializer<'de>
 					{
 						struct Visitor;
 
-						impl<'de> ::serde::de::Visitor<'de> for Visitor {
+						impl<'de> _serde::de::Visitor<'de> for Visitor {
 							type Value = Field__;
 
 							fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
@@ -200,35 +378,31 @@ pub fn derive(
 
 							fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
 							where
-								E: ::serde::de::Error
+								E: _serde::de::Error
 							{
 								match v {
 									#(#cases_u64,)*
-									_ => Err(::serde::de::Error::invalid_value(::serde::de::Unexpected::Unsigned(v), &"field index"))
+									_ => #unknown_index
 								}
 							}
 
 							fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
 							where
-								E: ::serde::de::Error
+								E: _serde::de::Error
 							{
 								match v {
 									#(#cases_str,)*
-									_ => Err(::serde::de::Error::unknown_variant(v, &FIELDS))
+									_ => #unknown_str
 								}
 							}
 
 							fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
 							where
-								E: ::serde::de::Error
+								E: _serde::de::Error
 							{
 								match v {
 									#(#cases_bytes,)*
-									// See https://github.com/serde-rs/serde/blob/e3eaa6a3dd6edd701476097182313cdbd73da78c/serde/src/de/impls.rs#L1664C33-L1667C34
-									_ => match str::from_utf8(v) {
-										Ok(v) => Err(::serde::de::Error::unknown_variant(v, &FIELDS)),
-										Err(_) => Err(::serde::de::Error::invalid_value(::serde::de::Unexpected::Bytes(v), &self))
-									}
+									_ => #unknown_bytes
 								}
 							}
 						}
@@ -239,11 +413,7 @@ pub fn derive(
 
 				#(#define_fields)*
 
-				while let Some(field) = map__.next_key()? {
-					match field {
-						#(#cases),*
-					}
-				}
+				#read_entries
 
 				Ok(#ident #variant_ext {
 					#(#unwrap_fields),*
@@ -3,7 +3,7 @@ use quote::quote;
 
 use crate::{
 	attributes::{SerializeAttributes, TypeAttributes},
-	de::{deserialize_seed, split_visitor_generics, Error},
+	de::{deserialize_seed, seed_mutability, split_visitor_generics, Error},
 	SerializedUnnamedField,
 };
 
@@ -17,114 +17,163 @@ pub fn derive(
 ) -> Result<TokenStream, Error> {
 	let name = attrs.name(ident);
 	let seed_ty = de.require_seed()?;
+	let mutability = seed_mutability(de);
 	let variant_ext = variant_ident.map(|i| {
 		quote! {
 			:: #i
 		}
 	});
 
-	if fields.len() == 1 {
-		let v = fields.first().unwrap();
-		let seed = deserialize_seed(ident, generics, de, &v.attrs, &v.ty)?;
-
-		if variant_ident.is_some() {
-			Ok(quote! {
-				::serde::de::VariantAccess::newtype_variant_seed(variant, #seed)
-					.map(#ident #variant_ext)
-			})
-		} else {
-			let (def_generics, impl_generics, ty_generics, where_clause, value_generics) =
-				split_visitor_generics(generics, de);
-
-			Ok(quote! {
-				struct NewtypeVisitor #def_generics {
-					seed: &'seed #seed_ty,
-					p: ::core::marker::PhantomData<#ident #value_generics>
+	// Skipped fields are initialized with their default value, the others
+	// with the given deserialized values.
+	let init_fields = |seed: TokenStream, mut values: Vec<TokenStream>| {
+		values.reverse();
+		fields
+			.iter()
+			.map(|f| {
+				if f.attrs.skip_deserializing {
+					f.attrs.default_value(seed.clone())
+				} else {
+					values.pop().unwrap()
 				}
+			})
+			.collect::<Vec<_>>()
+	};
 
-				impl #impl_generics ::serde::de::Visitor<'de> for NewtypeVisitor #ty_generics #where_clause {
-					type Value = #ident #value_generics;
+	let unskipped_fields: Vec<_> = fields
+		.iter()
+		.filter(|f| !f.attrs.skip_deserializing)
+		.collect();
 
-					fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-						write!(formatter, "a newtype struct")
-					}
+	match unskipped_fields.as_slice() {
+		[] => {
+			if variant_ident.is_some() {
+				let init_fields = init_fields(quote! { self.seed }, Vec::new());
 
-					fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
-					where
-						D: ::serde::Deserializer<'de>
-					{
-						Ok(#ident #variant_ext (
-							::serde::de::DeserializeSeed::deserialize(#seed, deserializer)?
-						))
-					}
-				}
+				Ok(quote! {
+					_serde::de::VariantAccess::unit_variant(variant)?;
+					Ok(#ident #variant_ext (#(#init_fields),*))
+				})
+			} else {
+				let init_fields = init_fields(quote! { seed }, Vec::new());
 
-				deserializer.deserialize_newtype_struct(#name, NewtypeVisitor {
-					seed,
-					p: ::core::marker::PhantomData::<#ident #value_generics>
+				Ok(quote! {
+					deserializer.deserialize_unit_struct(#name, _serde_seeded::private::de::UnitVisitor)?;
+					Ok(#ident #variant_ext (#(#init_fields),*))
 				})
-			})
+			}
 		}
-	} else {
-		let count = fields.len();
-		let error_message = format!("{count} arguments");
-		let args: Vec<_> = fields
-			.iter()
-			.enumerate()
-			.map(|(i, v)| {
-				let seed = deserialize_seed(ident, generics, de, &v.attrs, &v.ty)?;
+		[v] => {
+			let seed = deserialize_seed(ident, generics, de, &v.attrs, &v.ty)?;
+
+			if variant_ident.is_some() {
+				let init_fields = init_fields(quote! { self.seed }, vec![quote! { value }]);
+
 				Ok(quote! {
-					seq.next_element_seed(#seed)?.ok_or_else(|| {
-						::serde::de::Error::invalid_length(
-							#i,
-							&#error_message
-						)
-					})?
+					let value = _serde::de::VariantAccess::newtype_variant_seed(variant, #seed)?;
+					Ok(#ident #variant_ext (#(#init_fields),*))
 				})
-			})
-			.collect::<Result<_, Error>>()?;
+			} else {
+				let (def_generics, impl_generics, ty_generics, where_clause, value_generics) =
+					split_visitor_generics(generics, de);
 
-		let (def_generics, impl_generics, ty_generics, where_clause, value_generics) =
-			split_visitor_generics(generics, de);
+				let init_fields = init_fields(
+					quote! { self.seed },
+					vec![quote! {
+						_serde::de::DeserializeSeed::deserialize(#seed, deserializer)?
+					}],
+				);
 
-		let visit = if variant_ident.is_some() {
-			quote! {
-				::serde::de::VariantAccess::tuple_variant(variant, #count, TupleVisitor {
-					seed: self.seed,
-					p: ::core::marker::PhantomData::<#ident #value_generics>
+				Ok(quote! {
+					struct NewtypeVisitor #def_generics {
+						seed: &'seed #mutability #seed_ty,
+						p: ::core::marker::PhantomData<#ident #value_generics>
+					}
+
+					impl #impl_generics _serde::de::Visitor<'de> for NewtypeVisitor #ty_generics #where_clause {
+						type Value = #ident #value_generics;
+
+						fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+							write!(formatter, "a newtype struct")
+						}
+
+						fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+						where
+							D: _serde::Deserializer<'de>
+						{
+							Ok(#ident #variant_ext (#(#init_fields),*))
+						}
+					}
+
+					deserializer.deserialize_newtype_struct(#name, NewtypeVisitor {
+						seed,
+						p: ::core::marker::PhantomData::<#ident #value_generics>
+					})
 				})
 			}
-		} else {
-			quote! {
-				deserializer.deserialize_tuple_struct(#name, #count, TupleVisitor {
-					seed,
-					p: ::core::marker::PhantomData::<#ident #value_generics>
+		}
+		unskipped_fields => {
+			let count = unskipped_fields.len();
+			let error_message = format!("{count} arguments");
+			let args: Vec<_> = unskipped_fields
+				.iter()
+				.enumerate()
+				.map(|(i, v)| {
+					let seed = deserialize_seed(ident, generics, de, &v.attrs, &v.ty)?;
+					Ok(quote! {
+						seq.next_element_seed(#seed)?.ok_or_else(|| {
+							_serde::de::Error::invalid_length(
+								#i,
+								&#error_message
+							)
+						})?
+					})
 				})
-			}
-		};
+				.collect::<Result<_, Error>>()?;
+			let init_fields = init_fields(quote! { self.seed }, args);
 
-		Ok(quote! {
-			struct TupleVisitor #def_generics {
-				seed: &'seed #seed_ty,
-				p: ::core::marker::PhantomData<#ident #value_generics>
-			}
+			let (def_generics, impl_generics, ty_generics, where_clause, value_generics) =
+				split_visitor_generics(generics, de);
 
-			impl #impl_generics ::serde::de::Visitor<'de> for TupleVisitor #ty_generics #where_clause {
-				type Value = #ident #value_generics;
+			let visit = if variant_ident.is_some() {
+				quote! {
+					_serde::de::VariantAccess::tuple_variant(variant, #count, TupleVisitor {
+						seed: self.seed,
+						p: ::core::marker::PhantomData::<#ident #value_generics>
+					})
+				}
+			} else {
+				quote! {
+					deserializer.deserialize_tuple_struct(#name, #count, TupleVisitor {
+						seed,
+						p: ::core::marker::PhantomData::<#ident #value_generics>
+					})
+				}
+			};
 
-				fn expecting(&self, formatter: &mut core::fmt::Formatter) -> ::core::fmt::Result {
-					write!(formatter, "a tuple")
+			Ok(quote! {
+				struct TupleVisitor #def_generics {
+					seed: &'seed #mutability #seed_ty,
+					p: ::core::marker::PhantomData<#ident #value_generics>
 				}
 
-				fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-				where
-					A: ::serde::de::SeqAccess<'de>
-				{
-					Ok(#ident #variant_ext (#(#args),*))
+				impl #impl_generics _serde::de::Visitor<'de> for TupleVisitor #ty_generics #where_clause {
+					type Value = #ident #value_generics;
+
+					fn expecting(&self, formatter: &mut core::fmt::Formatter) -> ::core::fmt::Result {
+						write!(formatter, "a tuple")
+					}
+
+					fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+					where
+						A: _serde::de::SeqAccess<'de>
+					{
+						Ok(#ident #variant_ext (#(#init_fields),*))
+					}
 				}
-			}
 
-			#visit
-		})
+				#visit
+			})
+		}
 	}
 }
@@ -1,14 +1,16 @@
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::spanned::Spanned;
 
 use crate::{
 	attributes,
-	utils::{SeedParam, SeededImplGenerics, SeededTypeGenerics},
+	utils::{declares_seed_lifetime, SeedParam, SeededImplGenerics, SeededTypeGenerics},
 	SerializedFields,
 };
 
-use self::attributes::{FieldAttributes, SerializeAttributes, TypeAttributes};
+use self::attributes::{
+	FieldAttributes, FieldFn, FromProxy, SeedMode, SerializeAttributes, TypeAttributes,
+};
 
 mod r#enum;
 mod fields;
@@ -24,6 +26,21 @@ pub enum Error {
 	#[error("cannot deserialize unit struct transparently")]
 	TransparentUnit(Span),
 
+	#[error("`other` variant must be a unit variant")]
+	OtherNotUnit(Span),
+
+	#[error("multiple `other` variants")]
+	MultipleOther(Span),
+
+	#[error("internally tagged enums do not support tuple variants")]
+	InternallyTaggedTuple(Span),
+
+	#[error("`flatten` cannot be used with `deny_unknown_fields`")]
+	FlattenDenyUnknownFields(Span),
+
+	#[error("untagged enums do not support mutable seeds, as failed variants would leave their changes in the seed")]
+	UntaggedMutableSeed(Span),
+
 	#[error(transparent)]
 	Attribute(#[from] attributes::Error),
 }
@@ -34,6 +51,11 @@ impl Error {
 			Self::Union(s) => *s,
 			Self::TransparentEnum(s) => *s,
 			Self::TransparentUnit(s) => *s,
+			Self::OtherNotUnit(s) => *s,
+			Self::MultipleOther(s) => *s,
+			Self::InternallyTaggedTuple(s) => *s,
+			Self::FlattenDenyUnknownFields(s) => *s,
+			Self::UntaggedMutableSeed(s) => *s,
 			Self::Attribute(e) => e.span(),
 		}
 	}
@@ -46,10 +68,30 @@ pub fn derive(input: syn::DeriveInput) -> Result<TokenStream, Error> {
 
 	let mut tokens = TokenStream::new();
 
-	for de in &attrs.de {
+	// Without seed type, the implementation is generic over the seed type.
+	let bounded_types = match attrs.from.as_ref().or(attrs.try_from.as_ref()) {
+		Some(proxy) => vec![proxy.clone()],
+		None => crate::field_types(&input.data, |f| {
+			!f.skip_deserializing && f.deserialize_with.is_none() && f.seed.is_none()
+		})?,
+	};
+	let generic_seed = |de: &SerializeAttributes| {
+		let seeded_bound = seeded_bound(de, &syn::parse_quote!(__Q));
+		de.or_generic_seed(&bounded_types, |ty| quote! { #ty: #seeded_bound })
+	};
+	let des: Vec<_> = if attrs.de.is_empty() {
+		vec![generic_seed(&SerializeAttributes::default())]
+	} else {
+		attrs.de.iter().map(generic_seed).collect()
+	};
+
+	for de in &des {
 		let seed_ty = de.require_seed()?;
+		let (_, seeded_fn) = seeded_trait(de);
+		let seeded_bound = seeded_bound(de, seed_ty);
+		let seed_param = seed_param(de, seed_ty);
 		let additional_predicates =
-			additional_predicates(&input.generics, seed_ty, &de.override_bounds);
+			additional_predicates(&input.generics, &seeded_bound, &de.override_bounds);
 		let mut generics = input.generics.clone();
 		generics
 			.make_where_clause()
@@ -64,26 +106,37 @@ pub fn derive(input: syn::DeriveInput) -> Result<TokenStream, Error> {
 			.predicates
 			.extend(de.override_bounds.iter().cloned());
 
-		let body = if attrs.transparent {
+		let body = if let Some(proxy) = attrs.proxy_from()? {
+			match proxy {
+				FromProxy::From(ty) => quote! {
+					let value = <#ty as #seeded_bound>::#seeded_fn(seed, deserializer)?;
+					Ok(<Self as ::core::convert::From<#ty>>::from(value))
+				},
+				FromProxy::TryFrom(ty) => quote! {
+					let value = <#ty as #seeded_bound>::#seeded_fn(seed, deserializer)?;
+					<Self as ::core::convert::TryFrom<#ty>>::try_from(value).map_err(_serde::de::Error::custom)
+				},
+			}
+		} else if attrs.transparent {
 			match &input.data {
 				syn::Data::Struct(s) => {
-					let fields = SerializedFields::new(&s.fields)?;
+					attrs.reject_enum_repr()?;
+					let fields = SerializedFields::new(&s.fields, attrs.rename_all)?;
 
 					match fields {
 						SerializedFields::Unit => return Err(Error::TransparentUnit(input.span())),
 						SerializedFields::Named(fields) => {
 							let init_fields = fields.iter().map(|f| {
 								let f_ident = &f.id;
-								if f.attrs.skip {
+								if f.attrs.skip_deserializing {
+									let default = f.attrs.default_value(quote! { seed });
 									quote! {
-										#f_ident: Default::default()
+										#f_ident: #default
 									}
 								} else {
+									let deserialize = transparent_deserializer(de, &f.attrs);
 									quote! {
-										#f_ident: ::serde_seeded::DeserializeSeeded::deserialize_seeded(
-											seed,
-											deserializer
-										)?
+										#f_ident: #deserialize?
 									}
 								}
 							});
@@ -96,17 +149,11 @@ pub fn derive(input: syn::DeriveInput) -> Result<TokenStream, Error> {
 						}
 						SerializedFields::Unnamed(fields) => {
 							let init_fields = fields.iter().map(|f| {
-								if f.attrs.skip {
-									quote! {
-										Default::default()
-									}
+								if f.attrs.skip_deserializing {
+									f.attrs.default_value(quote! { seed })
 								} else {
-									quote! {
-										::serde_seeded::DeserializeSeeded::deserialize_seeded(
-											seed,
-											deserializer
-										)?
-									}
+									let deserialize = transparent_deserializer(de, &f.attrs);
+									quote! { #deserialize? }
 								}
 							});
 
@@ -122,7 +169,8 @@ pub fn derive(input: syn::DeriveInput) -> Result<TokenStream, Error> {
 		} else {
 			match &input.data {
 				syn::Data::Struct(s) => {
-					let fields = SerializedFields::new(&s.fields)?;
+					attrs.reject_enum_repr()?;
+					let fields = SerializedFields::new(&s.fields, attrs.rename_all)?;
 					fields::derive(ident, &generics, &attrs, de, None, &fields)?
 				}
 				syn::Data::Enum(e) => r#enum::derive(ident, &generics, &attrs, de, e)?,
@@ -130,29 +178,193 @@ pub fn derive(input: syn::DeriveInput) -> Result<TokenStream, Error> {
 			}
 		};
 
-		let impl_generics = SeededImplGenerics::new(&generics)
+		// The validation function is called on the deserialized value before
+		// returning it.
+		let body = match &attrs.validate {
+			Some(path) => quote! {
+				let deserialize = || -> Result<Self, D::Error> {
+					#body
+				};
+
+				let value = deserialize()?;
+
+				if let Err(e) = #path(&value, seed) {
+					return Err(_serde::de::Error::custom(e));
+				}
+
+				Ok(value)
+			},
+			None => body,
+		};
+
+		let mut impl_generics = SeededImplGenerics::new(&generics)
 			.with(SeedParam::DeLifetime)
 			.with_extra_params(&de.params);
+		if de.mode == SeedMode::Ref {
+			// The value may borrow from the seed.
+			impl_generics = impl_generics.with(SeedParam::SeedLifetime);
+		}
 		let (_, ty_generics, where_clause) = generics.split_for_impl();
 
 		tokens.extend(quote! {
-			impl #impl_generics ::serde_seeded::DeserializeSeeded<'de, #seed_ty> for #ident #ty_generics #where_clause {
-				fn deserialize_seeded<D>(
-					seed: &#seed_ty,
+			impl #impl_generics #seeded_bound for #ident #ty_generics #where_clause {
+				fn #seeded_fn<D>(
+					seed: #seed_param,
 					deserializer: D
-				) -> Result<Self, D::Error> where D: ::serde::Deserializer<'de> {
+				) -> Result<Self, D::Error> where D: _serde::Deserializer<'de> {
 					#body
 				}
 			}
 		});
+
+		// Remote types are deserialized through the mirror type, then
+		// converted by moving each field, with an inherent function of the
+		// mirror type usable with the `with` field attribute. Since fields
+		// read through a getter may be private, the mirror type must then
+		// provide a `From` conversion instead.
+		if let Some(remote) = &attrs.remote {
+			let (type_impl_generics, _, type_where_clause) = input.generics.split_for_impl();
+			let extra_params = &de.params;
+			let predicates = where_clause.map(|w| &w.predicates);
+			let conversion = if has_getter(&input.data)? {
+				quote! { ::core::convert::From::from(value) }
+			} else {
+				remote_conversion(ident, remote, &input.data)
+			};
+			let seed_lft = (de.mode == SeedMode::Ref && !declares_seed_lifetime(&input.generics))
+				.then(|| quote! { 'seed, });
+
+			tokens.extend(quote! {
+				impl #type_impl_generics #ident #ty_generics #type_where_clause {
+					pub fn #seeded_fn<'de, #seed_lft #(#extra_params,)* __D>(
+						seed: #seed_param,
+						deserializer: __D
+					) -> Result<#remote #ty_generics, __D::Error>
+					where
+						__D: _serde::Deserializer<'de>,
+						#predicates
+					{
+						let value = <Self as #seeded_bound>::#seeded_fn(seed, deserializer)?;
+						Ok(#conversion)
+					}
+				}
+			});
+		}
 	}
 
-	Ok(tokens)
+	Ok(attrs.wrap_in_const(tokens))
+}
+
+/// Checks if any field of the mirror type is read through a `getter`.
+fn has_getter(data: &syn::Data) -> Result<bool, Error> {
+	let fields: Vec<&syn::Field> = match data {
+		syn::Data::Struct(s) => s.fields.iter().collect(),
+		syn::Data::Enum(e) => e.variants.iter().flat_map(|v| &v.fields).collect(),
+		syn::Data::Union(_) => Vec::new(),
+	};
+
+	for f in fields {
+		if FieldAttributes::parse_attributes(&f.attrs)?
+			.getter
+			.is_some()
+		{
+			return Ok(true);
+		}
+	}
+
+	Ok(false)
+}
+
+/// Converts the mirror `value` into the remote type, moving each field.
+fn remote_conversion(ident: &syn::Ident, remote: &syn::Path, data: &syn::Data) -> TokenStream {
+	fn bindings(fields: &syn::Fields) -> TokenStream {
+		match fields {
+			syn::Fields::Unit => quote! {},
+			syn::Fields::Unnamed(fields) => {
+				let args = (0..fields.unnamed.len()).map(|i| format_ident!("arg_{i}"));
+				quote! { ( #(#args),* ) }
+			}
+			syn::Fields::Named(fields) => {
+				let args = fields.named.iter().map(|f| &f.ident);
+				quote! { { #(#args),* } }
+			}
+		}
+	}
+
+	match data {
+		syn::Data::Struct(s) => {
+			let args = bindings(&s.fields);
+			quote! {
+				{
+					let #ident #args = value;
+					#remote #args
+				}
+			}
+		}
+		syn::Data::Enum(e) => {
+			let cases = e.variants.iter().map(|v| {
+				let variant_ident = &v.ident;
+				let args = bindings(&v.fields);
+				quote! {
+					#ident::#variant_ident #args => #remote::#variant_ident #args
+				}
+			});
+
+			quote! {
+				match value {
+					#(#cases),*
+				}
+			}
+		}
+		syn::Data::Union(_) => unreachable!(),
+	}
+}
+
+/// Seeded deserialization trait implemented according to the given
+/// attributes, with its method.
+fn seeded_trait(de: &SerializeAttributes) -> (TokenStream, syn::Ident) {
+	match de.mode {
+		SeedMode::Shared => (
+			quote! { _serde_seeded::DeserializeSeeded },
+			format_ident!("deserialize_seeded"),
+		),
+		SeedMode::Mut => (
+			quote! { _serde_seeded::DeserializeSeededMut },
+			format_ident!("deserialize_seeded_mut"),
+		),
+		SeedMode::Ref => (
+			quote! { _serde_seeded::DeserializeSeededRef },
+			format_ident!("deserialize_seeded_ref"),
+		),
+	}
+}
+
+/// Seeded deserialization trait bound for the given seed type.
+fn seeded_bound(de: &SerializeAttributes, seed_ty: &syn::Type) -> TokenStream {
+	let (seeded_trait, _) = seeded_trait(de);
+	match de.mode {
+		SeedMode::Ref => quote! { #seeded_trait<'de, 'seed, #seed_ty> },
+		_ => quote! { #seeded_trait<'de, #seed_ty> },
+	}
+}
+
+/// Type of the seed parameter of the seeded deserialization method.
+fn seed_param(de: &SerializeAttributes, seed_ty: &syn::Type) -> TokenStream {
+	match de.mode {
+		SeedMode::Shared => quote! { &#seed_ty },
+		SeedMode::Mut => quote! { &mut #seed_ty },
+		SeedMode::Ref => quote! { &'seed #seed_ty },
+	}
+}
+
+/// Mutability of the references to the seed.
+fn seed_mutability(de: &SerializeAttributes) -> Option<syn::Token![mut]> {
+	(de.mode == SeedMode::Mut).then(Default::default)
 }
 
 fn additional_predicates(
 	generics: &syn::Generics,
-	seed_ty: &syn::Type,
+	seeded_bound: &TokenStream,
 	override_bounds: &[syn::WherePredicate],
 ) -> Vec<syn::WherePredicate> {
 	let mut result = Vec::new();
@@ -173,7 +385,7 @@ fn additional_predicates(
 
 			result.push(
 				syn::parse2(quote! {
-					#ident: ::serde_seeded::DeserializeSeeded<'de, #seed_ty>
+					#ident: #seeded_bound
 				})
 				.unwrap(),
 			)
@@ -183,6 +395,30 @@ fn additional_predicates(
 	result
 }
 
+/// Deserializes the field of a transparent struct.
+fn transparent_deserializer(de: &SerializeAttributes, attrs: &FieldAttributes) -> TokenStream {
+	let seed_expr = attrs.seed_expr(&de.components, quote! { _serde::de::Error });
+	let (seeded_trait, seeded_fn) = seeded_trait(de);
+
+	match &attrs.deserialize_with {
+		Some(FieldFn::Unseeded(path)) => quote! {
+			#path(deserializer)
+		},
+		Some(FieldFn::Seeded(path)) => quote! {
+			#path(#seed_expr, deserializer)
+		},
+		Some(FieldFn::Module(module)) => quote! {
+			#module::#seeded_fn(#seed_expr, deserializer)
+		},
+		None => quote! {
+			#seeded_trait::#seeded_fn(
+				#seed_expr,
+				deserializer
+			)
+		},
+	}
+}
+
 fn deserialize_seed(
 	ident: &syn::Ident,
 	generics: &syn::Generics,
@@ -190,8 +426,12 @@ fn deserialize_seed(
 	attrs: &FieldAttributes,
 	ty: &syn::Type,
 ) -> Result<TokenStream, Error> {
-	match &attrs.with {
-		Some(id) => {
+	let seed_expr = attrs.seed_expr(&de.components, quote! { _serde::de::Error });
+	let (_, seeded_fn) = seeded_trait(de);
+	let mutability = seed_mutability(de);
+
+	match &attrs.deserialize_with {
+		Some(f) => {
 			let seed_ty = de.require_seed()?;
 
 			let def_generics = SeededImplGenerics::new(generics)
@@ -206,24 +446,35 @@ fn deserialize_seed(
 				.with_extra_params(&de.params);
 			let target_generics = SeededTypeGenerics::new(generics);
 
+			let deserialize = match f {
+				FieldFn::Unseeded(path) => quote! {
+					#path(deserializer)
+				},
+				FieldFn::Seeded(path) => quote! {
+					let seed = self.seed;
+					#path(#seed_expr, deserializer)
+				},
+				FieldFn::Module(module) => quote! {
+					let seed = self.seed;
+					#module::#seeded_fn(#seed_expr, deserializer)
+				},
+			};
+
 			Ok(quote! {
 				{
 					struct DeserializeWith #def_generics #where_clause {
-						seed: &'seed #seed_ty,
+						seed: &'seed #mutability #seed_ty,
 						p: ::core::marker::PhantomData<(&'de (), #ident #target_generics)>
 					}
 
-					impl #impl_generics ::serde::de::DeserializeSeed<'de> for DeserializeWith #ty_generics #where_clause {
+					impl #impl_generics _serde::de::DeserializeSeed<'de> for DeserializeWith #ty_generics #where_clause {
 						type Value = #ty;
 
 						fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
 						where
-							D: ::serde::Deserializer<'de>
+							D: _serde::Deserializer<'de>
 						{
-							#id :: deserialize_seeded(
-								self.seed,
-								deserializer
-							)
+							#deserialize
 						}
 					}
 
@@ -234,9 +485,25 @@ fn deserialize_seed(
 				}
 			})
 		}
-		None => Ok(quote! {
-			::serde_seeded::de::Seed::<_, #ty>::new(self.seed)
-		}),
+		None => {
+			let seed = match de.mode {
+				SeedMode::Shared => quote! { _serde_seeded::de::Seed },
+				SeedMode::Mut => quote! { _serde_seeded::de::SeedMut },
+				SeedMode::Ref => quote! { _serde_seeded::de::SeedRef },
+			};
+
+			match &attrs.seed {
+				Some(_) => Ok(quote! {
+					#seed::<_, #ty>::new({
+						let seed = &#mutability *self.seed;
+						#seed_expr
+					})
+				}),
+				None => Ok(quote! {
+					#seed::<_, #ty>::new(self.seed)
+				}),
+			}
+		}
 	}
 }
 
@@ -5,6 +5,7 @@
 //!
 //! [`serde-seeded`]: <https://crates.io/crates/serde-seeded>
 use attributes::FieldAttributes;
+use case::RenameRule;
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use proc_macro_error::{abort, proc_macro_error};
@@ -12,6 +13,7 @@ use quote::format_ident;
 use syn::{parse_macro_input, spanned::Spanned};
 
 pub(crate) mod attributes;
+mod case;
 mod de;
 mod ser;
 pub(crate) mod utils;
@@ -46,6 +48,30 @@ pub fn derive_deserialize(input: TokenStream) -> TokenStream {
 	}
 }
 
+/// Collects the types of the fields selected by `filter`.
+///
+/// Those are the types that must implement the seeded traits for the
+/// implementations generic over the seed type.
+fn field_types(
+	data: &syn::Data,
+	filter: impl Fn(&FieldAttributes) -> bool,
+) -> Result<Vec<syn::Type>, attributes::Error> {
+	let fields: Vec<&syn::Field> = match data {
+		syn::Data::Struct(s) => s.fields.iter().collect(),
+		syn::Data::Enum(e) => e.variants.iter().flat_map(|v| &v.fields).collect(),
+		syn::Data::Union(_) => Vec::new(),
+	};
+
+	let mut result = Vec::new();
+	for f in fields {
+		if filter(&FieldAttributes::parse_attributes(&f.attrs)?) {
+			result.push(f.ty.clone())
+		}
+	}
+
+	Ok(result)
+}
+
 enum SerializedFields {
 	Unit,
 	Unnamed(Vec<SerializedUnnamedField>),
@@ -53,7 +79,9 @@ enum SerializedFields {
 }
 
 impl SerializedFields {
-	fn new(fields: &syn::Fields) -> Result<Self, attributes::Error> {
+	/// Collects the given fields, named according to `rename_rule` unless
+	/// renamed explicitly.
+	fn new(fields: &syn::Fields, rename_rule: RenameRule) -> Result<Self, attributes::Error> {
 		match fields {
 			syn::Fields::Unit => Ok(Self::Unit),
 			syn::Fields::Unnamed(fields) => fields
@@ -66,6 +94,7 @@ impl SerializedFields {
 						index: i.into(),
 						id: format_ident!("arg_{i}"),
 						ty: f.ty.clone(),
+						span: f.span(),
 					})
 				})
 				.collect::<Result<_, _>>()
@@ -79,6 +108,7 @@ impl SerializedFields {
 						id: f.ident.clone().unwrap(),
 						ty: f.ty.clone(),
 						span: f.span(),
+						rename_rule,
 					})
 				})
 				.collect::<Result<_, _>>()
@@ -92,6 +122,7 @@ struct SerializedUnnamedField {
 	index: syn::Index,
 	id: syn::Ident,
 	ty: syn::Type,
+	span: Span,
 }
 
 struct SerializedNamedField {
@@ -99,10 +130,19 @@ struct SerializedNamedField {
 	id: syn::Ident,
 	ty: syn::Type,
 	span: Span,
+	rename_rule: RenameRule,
 }
 
 impl SerializedNamedField {
 	pub fn name(&self) -> String {
-		self.attrs.name(&self.id)
+		self.attrs.name(&self.id, self.rename_rule)
+	}
+
+	/// Checks if, when missing, the field must be deserialized as `None`.
+	///
+	/// This is the case for any field of type `Option<T>` that is not
+	/// annotated with `#[seeded(required)]`.
+	pub fn is_optional(&self) -> bool {
+		!self.attrs.required && utils::is_option(&self.ty)
 	}
 }
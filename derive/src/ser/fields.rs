@@ -1,11 +1,11 @@
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote, ToTokens};
 
-use crate::SerializedFields;
+use crate::{SerializedFields, SerializedNamedField};
 
 use super::{
-	attributes::{SerializeAttributes, TypeAttributes},
-	value_serializer, Error, SerializedVariant,
+	attributes::{FieldAttributes, SerializeAttributes, TypeAttributes},
+	value_serializer, Error, Target,
 };
 
 pub fn derive(
@@ -13,59 +13,50 @@ pub fn derive(
 	generics: &syn::Generics,
 	attrs: &TypeAttributes,
 	ser: &SerializeAttributes,
-	variant: Option<SerializedVariant>,
+	target: Target,
 	fields: &SerializedFields,
 ) -> Result<TokenStream, Error> {
 	let name = attrs.name(ident);
 
 	match fields {
-		SerializedFields::Unit => match variant {
-			Some(variant) => {
-				let variant_index = variant.index;
-				let variant_name = variant.ident.to_string();
-
-				Ok(quote! {
-					serializer.serialize_unit_variant(#name, #variant_index, #variant_name)
-				})
-			}
-			None => Ok(quote! {
-				serializer.serialize_unit_struct(#name)
-			}),
-		},
+		SerializedFields::Unit => Ok(unit(&name, target)),
 		SerializedFields::Unnamed(fields) => {
-			let unskipped_fields_count = fields.iter().filter(|f| !f.attrs.skip).count();
+			let unskipped_fields_count =
+				fields.iter().filter(|f| !f.attrs.skip_serializing).count();
 
 			match unskipped_fields_count {
-				0 => match variant {
-					Some(variant) => {
-						let variant_index = variant.index;
-						let variant_name = variant.ident.to_string();
+				0 => Ok(unit(&name, target)),
+				1 => {
+					let field = fields.iter().find(|f| !f.attrs.skip_serializing).unwrap();
 
-						Ok(quote! {
-							serializer.serialize_unit_variant(#name, #variant_index, #variant_name)
-						})
+					if field.attrs.skip_serializing_if.is_some()
+						|| field.attrs.skip_serializing_if_seeded.is_some()
+					{
+						return Err(Error::SkipSerializingIfNewtype(field.span));
 					}
-					None => Ok(quote! {
-						serializer.serialize_unit_struct(#name)
-					}),
-				},
-				1 => {
-					let field = fields.iter().find(|f| !f.attrs.skip).unwrap();
 
-					match variant {
-						Some(variant) => {
-							let variant_index = variant.index;
-							let variant_name = variant.ident.to_string();
+					let field_accessor =
+						field_accessor(&target, &field.attrs, &field.id, &field.index);
 
-							let field_id = &field.id;
-							let value_serializer = value_serializer(
-								ident,
-								generics,
-								ser,
-								&field.attrs,
-								&field.ty,
-								quote! { #field_id },
-							)?;
+					let value_serializer = value_serializer(
+						ident,
+						generics,
+						ser,
+						&field.attrs,
+						&field.ty,
+						field_accessor,
+					)?;
+
+					match target {
+						Target::Struct => Ok(quote! {
+							serializer.serialize_newtype_struct(
+								#name,
+								&#value_serializer
+							)
+						}),
+						Target::Variant(variant) => {
+							let variant_index = variant.index;
+							let variant_name = &variant.name;
 
 							Ok(quote! {
 								serializer.serialize_newtype_variant(
@@ -76,190 +67,197 @@ pub fn derive(
 								)
 							})
 						}
-						None => {
-							let field_index = &field.index;
-							let value_serializer = value_serializer(
-								ident,
-								generics,
-								ser,
-								&field.attrs,
-								&field.ty,
-								quote! { &self.#field_index },
-							)?;
+						Target::InternallyTagged(variant, tag) => {
+							let variant_ident = &variant.ident;
+							let variant_name = &variant.name;
 
 							Ok(quote! {
-								serializer.serialize_newtype_struct(
-									#name,
-									&#value_serializer
+								_serde::Serialize::serialize(
+									&#value_serializer,
+									_serde_seeded::private::ser::TaggedSerializer::new(
+										#name,
+										#variant_ident,
+										#tag,
+										#variant_name,
+										serializer
+									)
 								)
 							})
 						}
+						Target::Untagged(_) => Ok(quote! {
+							_serde::Serialize::serialize(&#value_serializer, serializer)
+						}),
 					}
 				}
 				_ => {
-					let count = fields.len();
+					let skips =
+						skip_conditions(fields.iter().map(|f| {
+							(&f.attrs, field_accessor(&target, &f.attrs, &f.id, &f.index))
+						}));
+
+					let count_expr_terms = fields.iter().zip(&skips).filter_map(|(f, skip)| {
+						if f.attrs.skip_serializing {
+							return None;
+						}
+
+						match skip {
+							Some((skip, _)) => Some(quote! { if #skip { 0 } else { 1 } }),
+							None => Some(quote! { 1 }),
+						}
+					});
+
+					let count = quote! { 0 #( + #count_expr_terms )* };
+
+					let serialize_field = match target {
+						Target::Variant(_) => quote! {
+							_serde::ser::SerializeTupleVariant::serialize_field
+						},
+						Target::Untagged(_) => quote! {
+							_serde::ser::SerializeTuple::serialize_element
+						},
+						_ => quote! {
+							_serde::ser::SerializeTupleStruct::serialize_field
+						},
+					};
 
 					let ser_fields = fields
 						.iter()
-						.map(|f| {
-							if variant.is_some() {
-								let arg = &f.id;
-								let value_serializer = value_serializer(
-									ident,
-									generics,
-									ser,
-									&f.attrs,
-									&f.ty,
-									quote! { #arg },
-								)?;
+						.zip(&skips)
+						.filter(|(f, _)| !f.attrs.skip_serializing)
+						.map(|(f, skip)| {
+							let field_accessor = field_accessor(&target, &f.attrs, &f.id, &f.index);
 
-								Ok(quote! {
-									::serde::ser::SerializeTupleVariant::serialize_field(
-										&mut s,
-										&#value_serializer
-									)?;
-								})
-							} else {
-								let index = &f.index;
-								let value_serializer = value_serializer(
-									ident,
-									generics,
-									ser,
-									&f.attrs,
-									&f.ty,
-									quote! { &self.#index },
-								)?;
+							let value_serializer = value_serializer(
+								ident,
+								generics,
+								ser,
+								&f.attrs,
+								&f.ty,
+								field_accessor,
+							)?;
 
-								Ok(quote! {
-									::serde::ser::SerializeTupleStruct::serialize_field(
-										&mut s,
-										&#value_serializer
-									)?;
-								})
-							}
+							let serialize_field = quote! {
+								#serialize_field(
+									&mut s,
+									&#value_serializer
+								)?;
+							};
+
+							Ok(match skip {
+								Some((skip, _)) => quote! {
+									if !#skip {
+										#serialize_field
+									}
+								},
+								None => serialize_field,
+							})
 						})
 						.collect::<Result<Vec<_>, Error>>()?;
+					let skip_bindings = skips.iter().flatten().map(|(_, binding)| binding);
 
-					match variant {
-						Some(variant) => {
+					match target {
+						Target::Variant(variant) => {
 							let variant_index = variant.index;
-							let variant_name = variant.ident.to_string();
+							let variant_name = &variant.name;
 
 							Ok(quote! {
+								#(#skip_bindings)*
 								let mut s = serializer.serialize_tuple_variant(#name, #variant_index, #variant_name, #count)?;
 
 								#(#ser_fields)*
 
-								::serde::ser::SerializeTupleVariant::end(s)
+								_serde::ser::SerializeTupleVariant::end(s)
 							})
 						}
-						None => Ok(quote! {
+						Target::Untagged(_) => Ok(quote! {
+							#(#skip_bindings)*
+							let mut s = serializer.serialize_tuple(#count)?;
+
+							#(#ser_fields)*
+
+							_serde::ser::SerializeTuple::end(s)
+						}),
+						_ => Ok(quote! {
+							#(#skip_bindings)*
 							let mut s = serializer.serialize_tuple_struct(#name, #count)?;
 
 							#(#ser_fields)*
 
-							::serde::ser::SerializeTupleStruct::end(s)
+							_serde::ser::SerializeTupleStruct::end(s)
 						}),
 					}
 				}
 			}
 		}
 		SerializedFields::Named(fields) => {
-			// let unskipped_fields_count = fields.iter().filter(|f| !f.attrs.skip).count();
+			// let unskipped_fields_count = fields.iter().filter(|f| !f.attrs.skip_serializing).count();
+
+			if let Some(f) = fields
+				.iter()
+				.find(|f| !f.attrs.skip_serializing && f.attrs.flatten)
+			{
+				if let Target::Variant(_) = target {
+					return Err(Error::FlattenExternallyTagged(f.span));
+				}
+
+				return flattened_struct(ident, generics, ser, target, fields);
+			}
 
-			let count_expr_terms = fields.iter().filter_map(|f| {
-				if f.attrs.skip {
+			let skips = skip_conditions(
+				fields
+					.iter()
+					.map(|f| (&f.attrs, field_accessor(&target, &f.attrs, &f.id, &f.id))),
+			);
+
+			let count_expr_terms = fields.iter().zip(&skips).filter_map(|(f, skip)| {
+				if f.attrs.skip_serializing {
 					return None;
 				}
 
-				let field_ident = &f.id;
-				let field_accessor = if variant.is_some() {
-					quote! { #field_ident }
-				} else {
-					quote! { &self.#field_ident }
-				};
-
-				match &f.attrs.skip_serializing_if {
-					Some(path) => Some(quote! { if #path (#field_accessor) { 1 } else { 0 } }),
+				match skip {
+					Some((skip, _)) => Some(quote! { if #skip { 0 } else { 1 } }),
 					None => Some(quote! { 1 }),
 				}
 			});
 
 			let count_expr = quote! { 0 #( + #count_expr_terms )* };
 
+			let struct_trait = match target {
+				Target::Variant(_) => quote! { _serde::ser::SerializeStructVariant },
+				_ => quote! { _serde::ser::SerializeStruct },
+			};
+
 			let mut ser_fields = Vec::new();
-			for f in fields {
-				if f.attrs.skip {
+			for (f, skip) in fields.iter().zip(&skips) {
+				if f.attrs.skip_serializing {
 					continue;
 				}
 
 				let field_ident = &f.id;
 				let field_name = f.name();
-				let field_accessor = if variant.is_some() {
-					quote! { #field_ident }
-				} else {
-					quote! { &self.#field_ident }
-				};
+				let field_accessor = field_accessor(&target, &f.attrs, field_ident, field_ident);
 
-				let serialize_field = if variant.is_some() {
-					let value_serializer = value_serializer(
-						ident,
-						generics,
-						ser,
-						&f.attrs,
-						&f.ty,
-						field_accessor.clone(),
-					)?;
+				let value_serializer =
+					value_serializer(ident, generics, ser, &f.attrs, &f.ty, field_accessor)?;
 
-					quote! {
-						::serde::ser::SerializeStructVariant::serialize_field(
-							&mut s,
-							#field_name,
-							&#value_serializer
-						)?;
-					}
-				} else {
-					let value_serializer = value_serializer(
-						ident,
-						generics,
-						ser,
-						&f.attrs,
-						&f.ty,
-						field_accessor.clone(),
+				let serialize_field = quote! {
+					#struct_trait::serialize_field(
+						&mut s,
+						#field_name,
+						&#value_serializer
 					)?;
-
-					quote! {
-						::serde::ser::SerializeStruct::serialize_field(
-							&mut s,
-							#field_name,
-							&#value_serializer
-						)?;
-					}
 				};
 
-				let ser_field = match &f.attrs.skip_serializing_if {
-					Some(predicate) => {
-						let skip_field = if variant.is_some() {
-							quote! {
-								::serde::ser::SerializeStructVariant::skip_field(
-									&mut s,
-									#field_name
-								)?;
-							}
-						} else {
-							quote! {
-								::serde::ser::SerializeStruct::skip_field(
+				let ser_field = match skip {
+					Some((skip, _)) => {
+						quote! {
+							if #skip {
+								#struct_trait::skip_field(
 									&mut s,
 									#field_name
 								)?;
-							}
-						};
-
-						quote! {
-							if #predicate ( #field_accessor ) {
-								#serialize_field
 							} else {
-								#skip_field
+								#serialize_field
 							}
 						}
 					}
@@ -269,27 +267,211 @@ pub fn derive(
 				ser_fields.push(ser_field)
 			}
 
-			match variant {
-				Some(variant) => {
+			let skip_bindings = skips.iter().flatten().map(|(_, binding)| binding);
+
+			match target {
+				Target::Struct => Ok(quote! {
+					#(#skip_bindings)*
+					let mut s = serializer.serialize_struct(#name, #count_expr)?;
+
+					#(#ser_fields)*
+
+					_serde::ser::SerializeStruct::end(s)
+				}),
+				Target::Variant(variant) => {
 					let variant_index = variant.index;
-					let variant_name = variant.ident.to_string();
+					let variant_name = &variant.name;
 
 					Ok(quote! {
+						#(#skip_bindings)*
 						let mut s = serializer.serialize_struct_variant(#name, #variant_index, #variant_name, #count_expr)?;
 
 						#(#ser_fields)*
 
-						::serde::ser::SerializeStructVariant::end(s)
+						_serde::ser::SerializeStructVariant::end(s)
 					})
 				}
-				None => Ok(quote! {
-					let mut s = serializer.serialize_struct(#name, #count_expr)?;
+				Target::InternallyTagged(variant, tag) => {
+					let variant_name = &variant.name;
 
-					#(#ser_fields)*
+					Ok(quote! {
+						#(#skip_bindings)*
+						let mut s = serializer.serialize_struct(#name, 1 + #count_expr)?;
 
-					::serde::ser::SerializeStruct::end(s)
-				}),
+						_serde::ser::SerializeStruct::serialize_field(&mut s, #tag, #variant_name)?;
+
+						#(#ser_fields)*
+
+						_serde::ser::SerializeStruct::end(s)
+					})
+				}
+				Target::Untagged(variant) => {
+					let variant_name = &variant.name;
+
+					Ok(quote! {
+						#(#skip_bindings)*
+						let mut s = serializer.serialize_struct(#variant_name, #count_expr)?;
+
+						#(#ser_fields)*
+
+						_serde::ser::SerializeStruct::end(s)
+					})
+				}
+			}
+		}
+	}
+}
+
+/// Serializes a struct or variant with flattened fields, as a map.
+fn flattened_struct(
+	ident: &syn::Ident,
+	generics: &syn::Generics,
+	ser: &SerializeAttributes,
+	target: Target,
+	fields: &[SerializedNamedField],
+) -> Result<TokenStream, Error> {
+	let mut ser_fields = Vec::new();
+	for f in fields {
+		if f.attrs.skip_serializing {
+			continue;
+		}
+
+		let field_ident = &f.id;
+		let field_accessor = field_accessor(&target, &f.attrs, field_ident, field_ident);
+
+		let value_serializer = value_serializer(
+			ident,
+			generics,
+			ser,
+			&f.attrs,
+			&f.ty,
+			field_accessor.clone(),
+		)?;
+
+		let serialize_field = if f.attrs.flatten {
+			quote! {
+				_serde::Serialize::serialize(
+					&#value_serializer,
+					_serde_seeded::private::ser::FlatMapSerializer(&mut s)
+				)?;
+			}
+		} else {
+			let field_name = f.name();
+			quote! {
+				_serde::ser::SerializeMap::serialize_entry(
+					&mut s,
+					#field_name,
+					&#value_serializer
+				)?;
+			}
+		};
+
+		let ser_field = match f.attrs.skip_serializing_condition(&field_accessor) {
+			Some(condition) => {
+				quote! {
+					if !(#condition) {
+						#serialize_field
+					}
+				}
 			}
+			None => serialize_field,
+		};
+
+		ser_fields.push(ser_field)
+	}
+
+	let tag = match target {
+		Target::InternallyTagged(variant, tag) => {
+			let variant_name = &variant.name;
+			Some(quote! {
+				_serde::ser::SerializeMap::serialize_entry(&mut s, #tag, #variant_name)?;
+			})
 		}
+		_ => None,
+	};
+
+	Ok(quote! {
+		let mut s = serializer.serialize_map(None)?;
+
+		#tag
+
+		#(#ser_fields)*
+
+		_serde::ser::SerializeMap::end(s)
+	})
+}
+
+/// Binds the skip condition of each serialized field, given with an
+/// expression referencing its value, to a variable.
+///
+/// Each condition is evaluated once, before serializing any field, since it
+/// is needed to compute the number of serialized fields and conditions
+/// taking a mutable seed may have side effects.
+fn skip_conditions<'a>(
+	fields: impl Iterator<Item = (&'a FieldAttributes, TokenStream)>,
+) -> Vec<Option<(syn::Ident, TokenStream)>> {
+	fields
+		.enumerate()
+		.map(|(i, (attrs, value))| {
+			if attrs.skip_serializing {
+				return None;
+			}
+
+			attrs.skip_serializing_condition(&value).map(|condition| {
+				let skip = format_ident!("skip_{i}");
+				let binding = quote! {
+					let #skip = #condition;
+				};
+				(skip, binding)
+			})
+		})
+		.collect()
+}
+
+/// Expression referencing the value of a field.
+///
+/// This is the field binding when serializing a variant, or else the result
+/// of the field getter if any, or the field of `self`.
+pub fn field_accessor(
+	target: &Target,
+	attrs: &FieldAttributes,
+	binding: &syn::Ident,
+	member: impl ToTokens,
+) -> TokenStream {
+	if target.is_variant() {
+		quote! { #binding }
+	} else if let Some(getter) = &attrs.getter {
+		quote! { &#getter(self) }
+	} else {
+		quote! { &self.#member }
+	}
+}
+
+/// Serializes a unit struct or variant.
+fn unit(name: &str, target: Target) -> TokenStream {
+	match target {
+		Target::Struct => quote! {
+			serializer.serialize_unit_struct(#name)
+		},
+		Target::Variant(variant) => {
+			let variant_index = variant.index;
+			let variant_name = &variant.name;
+
+			quote! {
+				serializer.serialize_unit_variant(#name, #variant_index, #variant_name)
+			}
+		}
+		Target::InternallyTagged(variant, tag) => {
+			let variant_name = &variant.name;
+
+			quote! {
+				let mut s = serializer.serialize_struct(#name, 1)?;
+				_serde::ser::SerializeStruct::serialize_field(&mut s, #tag, #variant_name)?;
+				_serde::ser::SerializeStruct::end(s)
+			}
+		}
+		Target::Untagged(_) => quote! {
+			serializer.serialize_unit()
+		},
 	}
 }
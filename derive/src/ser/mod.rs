@@ -3,12 +3,14 @@ use quote::{format_ident, quote};
 use syn::spanned::Spanned;
 
 use crate::{
-	attributes::{self, TypeAttributes},
-	utils::{SeedParam, SeededImplGenerics, SeededTypeGenerics},
+	attributes::{self, EnumRepr, TypeAttributes},
+	utils::{PhantomParams, SeedParam, SeededImplGenerics, SeededTypeGenerics},
 	SerializedFields,
 };
 
-use self::attributes::{FieldAttributes, SerializeAttributes};
+use self::attributes::{
+	FieldAttributes, FieldFn, SeedMode, SerializeAttributes, VariantAttributes,
+};
 
 mod fields;
 
@@ -23,6 +25,18 @@ pub enum Error {
 	#[error("cannot serialize unit struct transparently")]
 	TransparentUnit(Span),
 
+	#[error("internally tagged enums do not support tuple variants")]
+	InternallyTaggedTuple(Span),
+
+	#[error("externally tagged enums do not support flattened fields")]
+	FlattenExternallyTagged(Span),
+
+	#[error("newtype fields cannot be conditionally skipped")]
+	SkipSerializingIfNewtype(Span),
+
+	#[error("`getter` is only allowed on remote types")]
+	GetterWithoutRemote(Span),
+
 	#[error(transparent)]
 	Attribute(#[from] attributes::Error),
 }
@@ -33,6 +47,10 @@ impl Error {
 			Self::Union(s) => *s,
 			Self::TransparentEnum(s) => *s,
 			Self::TransparentUnit(s) => *s,
+			Self::InternallyTaggedTuple(s) => *s,
+			Self::FlattenExternallyTagged(s) => *s,
+			Self::SkipSerializingIfNewtype(s) => *s,
+			Self::GetterWithoutRemote(s) => *s,
 			Self::Attribute(e) => e.span(),
 		}
 	}
@@ -43,12 +61,39 @@ pub fn derive(input: syn::DeriveInput) -> Result<TokenStream, Error> {
 
 	let attrs = TypeAttributes::parse_attributes(&input.attrs)?;
 
+	if attrs.remote.is_none() {
+		reject_getters(&input.data)?;
+	}
+
 	let mut tokens = TokenStream::new();
 
-	for ser in &attrs.ser {
+	// Without seed type, the implementation is generic over the seed type.
+	let bounded_types = match attrs.into.as_ref() {
+		Some(proxy) => vec![proxy.clone()],
+		None => crate::field_types(&input.data, |f| {
+			!f.skip_serializing && f.serialize_with.is_none() && f.seed.is_none()
+		})?,
+	};
+	let generic_seed = |ser: &SerializeAttributes| {
+		let (seeded_trait, _) = seeded_trait(ser);
+		ser.or_generic_seed(&bounded_types, |ty| quote! { #ty: #seeded_trait<__Q> })
+	};
+	let sers: Vec<_> = if attrs.ser.is_empty() {
+		vec![generic_seed(&SerializeAttributes::default())]
+	} else {
+		attrs.ser.iter().map(generic_seed).collect()
+	};
+
+	for ser in &sers {
 		let seed_ty = ser.require_seed()?;
-		let additional_predicates =
-			additional_predicates(&input.generics, seed_ty, &ser.override_bounds);
+		let (seeded_trait, seeded_fn) = seeded_trait(ser);
+		let mutability = seed_mutability(ser);
+		let additional_predicates = additional_predicates(
+			&input.generics,
+			&seeded_trait,
+			seed_ty,
+			&ser.override_bounds,
+		);
 		let mut generics = input.generics.clone();
 		generics
 			.make_where_clause()
@@ -63,26 +108,34 @@ pub fn derive(input: syn::DeriveInput) -> Result<TokenStream, Error> {
 			.predicates
 			.extend(ser.override_bounds.iter().cloned());
 
-		let body = if attrs.transparent {
+		let body = if let Some(into) = &attrs.into {
+			quote! {
+				#seeded_trait::#seeded_fn(
+					&<Self as ::core::convert::Into<#into>>::into(::core::clone::Clone::clone(self)),
+					seed,
+					serializer
+				)
+			}
+		} else if attrs.transparent {
 			match &input.data {
 				syn::Data::Struct(s) => {
-					let fields = SerializedFields::new(&s.fields)?;
+					attrs.reject_enum_repr()?;
+					let fields = SerializedFields::new(&s.fields, attrs.rename_all)?;
 
 					match fields {
 						SerializedFields::Unit => return Err(Error::TransparentUnit(input.span())),
 						SerializedFields::Named(fields) => {
 							let ser_field = fields.iter().find_map(|f| {
-								let f_ident = &f.id;
-								if f.attrs.skip {
+								if f.attrs.skip_serializing {
 									None
 								} else {
-									Some(quote! {
-										::serde_seeded::SerializeSeeded::serialize_seeded(
-											&self.#f_ident,
-											seed,
-											serializer
-										)
-									})
+									let accessor = fields::field_accessor(
+										&Target::Struct,
+										&f.attrs,
+										&f.id,
+										&f.id,
+									);
+									Some(transparent_serializer(ser, &f.attrs, accessor))
 								}
 							});
 
@@ -92,17 +145,16 @@ pub fn derive(input: syn::DeriveInput) -> Result<TokenStream, Error> {
 						}
 						SerializedFields::Unnamed(fields) => {
 							let ser_field = fields.iter().find_map(|f| {
-								let f_index = &f.index;
-								if f.attrs.skip {
+								if f.attrs.skip_serializing {
 									None
 								} else {
-									Some(quote! {
-										::serde_seeded::SerializeSeeded::serialize_seeded(
-											&self.#f_index,
-											seed,
-											serializer
-										)
-									})
+									let accessor = fields::field_accessor(
+										&Target::Struct,
+										&f.attrs,
+										&f.id,
+										&f.index,
+									);
+									Some(transparent_serializer(ser, &f.attrs, accessor))
 								}
 							});
 
@@ -118,85 +170,330 @@ pub fn derive(input: syn::DeriveInput) -> Result<TokenStream, Error> {
 		} else {
 			match &input.data {
 				syn::Data::Struct(s) => {
-					let fields = SerializedFields::new(&s.fields)?;
-					fields::derive(ident, &generics, &attrs, ser, None, &fields)?
+					attrs.reject_enum_repr()?;
+					let fields = SerializedFields::new(&s.fields, attrs.rename_all)?;
+					fields::derive(ident, &generics, &attrs, ser, Target::Struct, &fields)?
 				}
-				syn::Data::Enum(e) => {
-					let cases = e
-						.variants
-						.iter()
-						.enumerate()
-						.map(|(i, v)| {
-							let variant_ident = &v.ident;
-							let variant = SerializedVariant {
-								index: i as u32,
-								ident: variant_ident,
-							};
-							let fields = SerializedFields::new(&v.fields)?;
-							let ser_variant = fields::derive(
-								ident,
-								&generics,
-								&attrs,
-								ser,
-								Some(variant),
-								&fields,
-							)?;
-
-							let args = match &v.fields {
-								syn::Fields::Unit => {
-									quote! {}
-								}
-								syn::Fields::Unnamed(fields) => {
-									let args =
-										(0..fields.unnamed.len()).map(|i| format_ident!("arg_{i}"));
-
-									quote! {
-										( #(#args),* )
-									}
-								}
-								syn::Fields::Named(fields) => {
-									let args = fields.named.iter().map(|f| &f.ident);
+				syn::Data::Enum(e) => r#enum(ident, &generics, &attrs, ser, e)?,
+				syn::Data::Union(u) => return Err(Error::Union(u.union_token.span)),
+			}
+		};
 
-									quote! {
-										{ #(#args),* }
-									}
-								}
-							};
+		let (impl_generics, ty_generics, where_clause) = split_ser_generics(&generics, &ser.params);
 
-							Ok(quote! {
-								Self::#variant_ident #args => {
-									#ser_variant
-								}
-							})
-						})
-						.collect::<Result<Vec<_>, Error>>()?;
+		// Remote types are serialized through an inherent function of the
+		// mirror type, usable with the `with` field attribute.
+		if let Some(remote) = &attrs.remote {
+			let (type_impl_generics, _, type_where_clause) = input.generics.split_for_impl();
+			let extra_params = &ser.params;
+			let predicates = where_clause.map(|w| &w.predicates);
+
+			tokens.extend(quote! {
+				impl #type_impl_generics #ident #ty_generics #type_where_clause {
+					pub fn #seeded_fn<#(#extra_params,)* __S>(
+						value: &#remote #ty_generics,
+						seed: &#mutability #seed_ty,
+						serializer: __S
+					) -> Result<__S::Ok, __S::Error>
+					where
+						__S: _serde::Serializer,
+						#predicates
+					{
+						trait SerializeRemote<Q: ?Sized> {
+							fn serialize_remote<S>(
+								&self,
+								seed: &#mutability Q,
+								serializer: S
+							) -> Result<S::Ok, S::Error> where S: _serde::Serializer;
+						}
 
-					quote! {
-						match self {
-							#(#cases),*
+						impl #impl_generics SerializeRemote<#seed_ty> for #remote #ty_generics #where_clause {
+							fn serialize_remote<S>(
+								&self,
+								seed: &#mutability #seed_ty,
+								serializer: S
+							) -> Result<S::Ok, S::Error> where S: _serde::Serializer {
+								#body
+							}
 						}
+
+						SerializeRemote::serialize_remote(value, seed, serializer)
 					}
 				}
-				syn::Data::Union(u) => return Err(Error::Union(u.union_token.span)),
-			}
-		};
+			});
 
-		let (impl_generics, ty_generics, where_clause) = split_ser_generics(&generics, &ser.params);
+			continue;
+		}
 
 		tokens.extend(quote! {
-			impl #impl_generics ::serde_seeded::SerializeSeeded<#seed_ty> for #ident #ty_generics #where_clause {
-				fn serialize_seeded<S>(
+			impl #impl_generics #seeded_trait<#seed_ty> for #ident #ty_generics #where_clause {
+				fn #seeded_fn<S>(
 					&self,
-					seed: &#seed_ty,
+					seed: &#mutability #seed_ty,
 					serializer: S
-				) -> Result<S::Ok, S::Error> where S: ::serde::Serializer {
+				) -> Result<S::Ok, S::Error> where S: _serde::Serializer {
 					#body
 				}
 			}
 		});
 	}
 
-	Ok(tokens)
+	Ok(attrs.wrap_in_const(tokens))
+}
+
+/// Makes sure no field has a `getter`, which is only allowed on remote types.
+fn reject_getters(data: &syn::Data) -> Result<(), Error> {
+	let fields: Vec<&syn::Field> = match data {
+		syn::Data::Struct(s) => s.fields.iter().collect(),
+		syn::Data::Enum(e) => e.variants.iter().flat_map(|v| &v.fields).collect(),
+		syn::Data::Union(_) => Vec::new(),
+	};
+
+	for f in fields {
+		if FieldAttributes::parse_attributes(&f.attrs)?
+			.getter
+			.is_some()
+		{
+			return Err(Error::GetterWithoutRemote(f.span()));
+		}
+	}
+
+	Ok(())
+}
+
+fn r#enum(
+	ident: &syn::Ident,
+	generics: &syn::Generics,
+	attrs: &TypeAttributes,
+	ser: &SerializeAttributes,
+	e: &syn::DataEnum,
+) -> Result<TokenStream, Error> {
+	let name = attrs.name(ident);
+	let path = attrs.value_path(ident);
+
+	match attrs.enum_repr()? {
+		EnumRepr::External => {
+			let cases = variant_cases(ident, generics, attrs, ser, e, VariantTag::External)?;
+
+			Ok(quote! {
+				match self {
+					#(#cases),*
+				}
+			})
+		}
+		EnumRepr::Untagged => {
+			let cases = variant_cases(ident, generics, attrs, ser, e, VariantTag::None)?;
+
+			Ok(quote! {
+				match self {
+					#(#cases),*
+				}
+			})
+		}
+		EnumRepr::Internal { tag } => {
+			for v in &e.variants {
+				if let syn::Fields::Unnamed(fields) = &v.fields {
+					if fields.unnamed.len() != 1 {
+						return Err(Error::InternallyTaggedTuple(v.ident.span()));
+					}
+				}
+			}
+
+			let cases = variant_cases(ident, generics, attrs, ser, e, VariantTag::Internal(&tag))?;
+
+			Ok(quote! {
+				match self {
+					#(#cases),*
+				}
+			})
+		}
+		EnumRepr::Adjacent { tag, content } => {
+			let cases = e
+				.variants
+				.iter()
+				.map(|v| {
+					let variant_ident = &v.ident;
+					let variant_attrs = VariantAttributes::parse_attributes(&v.attrs)?;
+
+					if variant_attrs.skip {
+						return Ok(skipped_variant_case(ident, &path, variant_ident));
+					}
+
+					let variant_name = variant_attrs.name(variant_ident, attrs.rename_all);
+					let (_, store_seed, _) = stored_seed(ser, ser.require_seed()?);
+
+					if matches!(v.fields, syn::Fields::Unit) {
+						Ok(quote! {
+							#path::#variant_ident { .. } => {
+								let mut s = serializer.serialize_struct(#name, 1)?;
+								_serde::ser::SerializeStruct::serialize_field(&mut s, #tag, #variant_name)?;
+								_serde::ser::SerializeStruct::end(s)
+							}
+						})
+					} else {
+						Ok(quote! {
+							#path::#variant_ident { .. } => {
+								let mut s = serializer.serialize_struct(#name, 2)?;
+								_serde::ser::SerializeStruct::serialize_field(&mut s, #tag, #variant_name)?;
+								_serde::ser::SerializeStruct::serialize_field(&mut s, #content, &AdjacentContent {
+									seed: #store_seed,
+									value: self,
+									p: ::core::marker::PhantomData
+								})?;
+								_serde::ser::SerializeStruct::end(s)
+							}
+						})
+					}
+				})
+				.collect::<Result<Vec<_>, Error>>()?;
+
+			let content_cases = variant_cases(ident, generics, attrs, ser, e, VariantTag::None)?;
+
+			let seed_ty = ser.require_seed()?;
+			let (stored_seed_ty, _, load_seed) = stored_seed(ser, seed_ty);
+			let def_generics = SeededImplGenerics::new(generics)
+				.with(SeedParam::ValueLifetime)
+				.with_extra_params(&ser.params);
+			let impl_generics = SeededImplGenerics::new(generics)
+				.with(SeedParam::ValueLifetime)
+				.with_extra_params(&ser.params);
+			let ty_generics = SeededTypeGenerics::new(generics)
+				.with(SeedParam::ValueLifetime)
+				.with_extra_params(&ser.params);
+			let where_clause = generics.where_clause.as_ref();
+			let target_generics = SeededTypeGenerics::new(generics);
+			let extra_phantom = PhantomParams(&ser.params);
+
+			Ok(quote! {
+				struct AdjacentContent #def_generics #where_clause {
+					seed: #stored_seed_ty,
+					value: &'value #path #target_generics,
+					p: ::core::marker::PhantomData<#extra_phantom>
+				}
+
+				impl #impl_generics _serde::Serialize for AdjacentContent #ty_generics #where_clause {
+					fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+					where
+						S: _serde::Serializer
+					{
+						#load_seed
+
+						match self.value {
+							#(#content_cases),*
+						}
+					}
+				}
+
+				match self {
+					#(#cases),*
+				}
+			})
+		}
+	}
+}
+
+/// How the variant tag is serialized along with the variant fields.
+#[derive(Clone, Copy)]
+enum VariantTag<'a> {
+	External,
+	Internal(&'a str),
+	None,
+}
+
+/// Generates the match arms serializing each variant of the given enum.
+fn variant_cases(
+	ident: &syn::Ident,
+	generics: &syn::Generics,
+	attrs: &TypeAttributes,
+	ser: &SerializeAttributes,
+	e: &syn::DataEnum,
+	tag: VariantTag,
+) -> Result<Vec<TokenStream>, Error> {
+	let path = attrs.value_path(ident);
+
+	e.variants
+		.iter()
+		.enumerate()
+		.map(|(i, v)| {
+			let variant_ident = &v.ident;
+			let variant_attrs = VariantAttributes::parse_attributes(&v.attrs)?;
+
+			if variant_attrs.skip {
+				return Ok(skipped_variant_case(ident, &path, variant_ident));
+			}
+
+			let variant = SerializedVariant {
+				index: i as u32,
+				ident: variant_ident.to_string(),
+				name: variant_attrs.name(variant_ident, attrs.rename_all),
+			};
+			let fields = SerializedFields::new(
+				&v.fields,
+				variant_attrs.rename_all.or(attrs.rename_all_fields),
+			)?;
+			let target = match tag {
+				VariantTag::External => Target::Variant(&variant),
+				VariantTag::Internal(tag) => Target::InternallyTagged(&variant, tag),
+				VariantTag::None => Target::Untagged(&variant),
+			};
+			let ser_variant = fields::derive(ident, generics, attrs, ser, target, &fields)?;
+
+			// Fields skipped when serializing are not bound.
+			let args = match &fields {
+				SerializedFields::Unit => {
+					quote! {}
+				}
+				SerializedFields::Unnamed(fields) => {
+					let args = fields.iter().map(|f| {
+						let id = &f.id;
+						if f.attrs.skip_serializing {
+							quote! { _ }
+						} else {
+							quote! { #id }
+						}
+					});
+
+					quote! {
+						( #(#args),* )
+					}
+				}
+				SerializedFields::Named(fields) => {
+					let args = fields.iter().map(|f| {
+						let id = &f.id;
+						if f.attrs.skip_serializing {
+							quote! { #id: _ }
+						} else {
+							quote! { #id }
+						}
+					});
+
+					quote! {
+						{ #(#args),* }
+					}
+				}
+			};
+
+			Ok(quote! {
+				#path::#variant_ident #args => {
+					#ser_variant
+				}
+			})
+		})
+		.collect()
+}
+
+fn skipped_variant_case(
+	ident: &syn::Ident,
+	path: &syn::Path,
+	variant_ident: &syn::Ident,
+) -> TokenStream {
+	let message = format!("the enum variant {ident}::{variant_ident} cannot be serialized");
+
+	quote! {
+		#path::#variant_ident { .. } => {
+			Err(_serde::ser::Error::custom(#message))
+		}
+	}
 }
 
 fn split_ser_generics<'a>(
@@ -212,8 +509,58 @@ fn split_ser_generics<'a>(
 	(impl_generics, ty_generics, where_clause)
 }
 
+/// Seeded serialization trait implemented according to the given attributes,
+/// with its method.
+fn seeded_trait(ser: &SerializeAttributes) -> (TokenStream, syn::Ident) {
+	if ser.mode == SeedMode::Mut {
+		(
+			quote! { _serde_seeded::SerializeSeededMut },
+			format_ident!("serialize_seeded_mut"),
+		)
+	} else {
+		(
+			quote! { _serde_seeded::SerializeSeeded },
+			format_ident!("serialize_seeded"),
+		)
+	}
+}
+
+/// Mutability of the references to the seed.
+fn seed_mutability(ser: &SerializeAttributes) -> Option<syn::Token![mut]> {
+	(ser.mode == SeedMode::Mut).then(Default::default)
+}
+
+/// Type of the seed stored in the generated `Serialize` implementations, with
+/// the expression storing the `seed` variable and the statement binding
+/// `seed` back from `self.seed`.
+///
+/// Since `Serialize::serialize` only borrows `self`, mutable seeds are stored
+/// in a `RefCell`.
+fn stored_seed(
+	ser: &SerializeAttributes,
+	seed_ty: &syn::Type,
+) -> (TokenStream, TokenStream, TokenStream) {
+	if ser.mode == SeedMode::Mut {
+		(
+			quote! { ::core::cell::RefCell<&'value mut #seed_ty> },
+			quote! { ::core::cell::RefCell::new(&mut *seed) },
+			quote! {
+				let mut seed = self.seed.borrow_mut();
+				let seed = &mut **seed;
+			},
+		)
+	} else {
+		(
+			quote! { &'value #seed_ty },
+			quote! { seed },
+			quote! { let seed = self.seed; },
+		)
+	}
+}
+
 fn additional_predicates(
 	generics: &syn::Generics,
+	seeded_trait: &TokenStream,
 	seed_ty: &syn::Type,
 	override_bounds: &[syn::WherePredicate],
 ) -> Vec<syn::WherePredicate> {
@@ -235,7 +582,7 @@ fn additional_predicates(
 
 			result.push(
 				syn::parse2(quote! {
-					#ident: ::serde_seeded::SerializeSeeded<#seed_ty>
+					#ident: #seeded_trait<#seed_ty>
 				})
 				.unwrap(),
 			)
@@ -245,10 +592,63 @@ fn additional_predicates(
 	result
 }
 
-#[derive(Clone, Copy)]
-pub struct SerializedVariant<'a> {
+pub struct SerializedVariant {
+	/// Declaration index of the variant, skipped variants included.
 	index: u32,
-	ident: &'a syn::Ident,
+	ident: String,
+	name: String,
+}
+
+/// How fields are serialized.
+#[derive(Clone, Copy)]
+pub enum Target<'a> {
+	/// Fields of a struct.
+	Struct,
+
+	/// Fields of an externally tagged variant.
+	Variant(&'a SerializedVariant),
+
+	/// Fields of an internally tagged variant, with the given tag.
+	InternallyTagged(&'a SerializedVariant, &'a str),
+
+	/// Fields of a variant, without its tag.
+	Untagged(&'a SerializedVariant),
+}
+
+impl Target<'_> {
+	fn is_variant(&self) -> bool {
+		!matches!(self, Self::Struct)
+	}
+}
+
+/// Serializes the field of a transparent struct, given an expression
+/// referencing its value.
+fn transparent_serializer(
+	ser: &SerializeAttributes,
+	attrs: &FieldAttributes,
+	value: TokenStream,
+) -> TokenStream {
+	let seed_expr = attrs.seed_expr(&ser.components, quote! { _serde::ser::Error });
+	let (seeded_trait, seeded_fn) = seeded_trait(ser);
+
+	match &attrs.serialize_with {
+		Some(FieldFn::Unseeded(path)) => quote! {
+			#path(#value, serializer)
+		},
+		Some(FieldFn::Seeded(path)) => quote! {
+			#path(#value, #seed_expr, serializer)
+		},
+		Some(FieldFn::Module(module)) => quote! {
+			#module::#seeded_fn(#value, #seed_expr, serializer)
+		},
+		None => quote! {
+			#seeded_trait::#seeded_fn(
+				#value,
+				#seed_expr,
+				serializer
+			)
+		},
+	}
 }
 
 fn value_serializer(
@@ -259,43 +659,70 @@ fn value_serializer(
 	ty: &syn::Type,
 	value: TokenStream,
 ) -> Result<TokenStream, Error> {
-	match &attrs.with {
-		Some(id) => {
+	let seed_expr = attrs.seed_expr(&ser.components, quote! { _serde::ser::Error });
+	let (_, seeded_fn) = seeded_trait(ser);
+
+	match &attrs.serialize_with {
+		Some(f) => {
 			let seed_ty = ser.require_seed()?;
-			let def_generics = SeededImplGenerics::new(generics).with(SeedParam::ValueLifetime);
+			let (stored_seed_ty, store_seed, load_seed) = stored_seed(ser, seed_ty);
+			let def_generics = SeededImplGenerics::new(generics)
+				.with(SeedParam::ValueLifetime)
+				.with_extra_params(&ser.params);
 			let impl_generics = SeededImplGenerics::new(generics)
 				.with(SeedParam::ValueLifetime)
 				.with_extra_params(&ser.params);
-			let ty_generics = SeededTypeGenerics::new(generics).with(SeedParam::ValueLifetime);
+			let ty_generics = SeededTypeGenerics::new(generics)
+				.with(SeedParam::ValueLifetime)
+				.with_extra_params(&ser.params);
 			let where_clause = generics.where_clause.as_ref();
 			let target_generics = SeededTypeGenerics::new(generics);
+			let extra_phantom = PhantomParams(&ser.params);
+
+			let serialize = match f {
+				FieldFn::Unseeded(path) => quote! {
+					#path(self.value, serializer)
+				},
+				FieldFn::Seeded(path) => quote! {
+					#load_seed
+					#path(self.value, #seed_expr, serializer)
+				},
+				FieldFn::Module(module) => quote! {
+					#load_seed
+					#module::#seeded_fn(self.value, #seed_expr, serializer)
+				},
+			};
 
 			Ok(quote! {
 				{
 					struct SerializeWith #def_generics #where_clause {
+						seed: #stored_seed_ty,
 						value: &'value #ty,
-						p: ::core::marker::PhantomData<#ident #target_generics>
+						p: ::core::marker::PhantomData<(#ident #target_generics, #extra_phantom)>
 					}
 
-					impl #impl_generics ::serde_seeded::SerializeSeeded<#seed_ty> for SerializeWith #ty_generics #where_clause {
-						fn serialize_seeded<S>(&self, seed: &#seed_ty, serializer: S) -> Result<S::Ok, S::Error>
+					impl #impl_generics _serde::Serialize for SerializeWith #ty_generics #where_clause {
+						fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 						where
-							S: ::serde::Serializer
+							S: _serde::Serializer
 						{
-							#id :: serialize_seeded (
-								self.value,
-								seed,
-								serializer
-							)
+							#serialize
 						}
 					}
 
-					::serde_seeded::ser::Seeded::new(seed, SerializeWith { value: #value, p: ::core::marker::PhantomData::<#ident #target_generics> })
+					SerializeWith {
+						seed: #store_seed,
+						value: #value,
+						p: ::core::marker::PhantomData::<(#ident #target_generics, #extra_phantom)>
+					}
 				}
 			})
 		}
+		None if ser.mode == SeedMode::Mut => Ok(quote! {
+			_serde_seeded::ser::SeededMut::new(#seed_expr, #value)
+		}),
 		None => Ok(quote! {
-			::serde_seeded::ser::Seeded::new(seed, #value)
+			_serde_seeded::ser::SeededRef::new(#seed_expr, #value)
 		}),
 	}
 }
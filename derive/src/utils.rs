@@ -154,7 +154,7 @@ impl ToTokens for SeededImplGenerics<'_> {
 			trailing_or_empty = true;
 		}
 
-		if self.seed_params.seed_lft {
+		if self.seed_params.seed_lft && !declares_seed_lifetime(self.generics) {
 			syn::Lifetime::new("'seed", Span::call_site()).to_tokens(tokens);
 			<syn::Token![,]>::default().to_tokens(tokens);
 			trailing_or_empty = true;
@@ -292,7 +292,7 @@ impl ToTokens for SeededTypeGenerics<'_> {
 			trailing_or_empty = true;
 		}
 
-		if self.params.seed_lft {
+		if self.params.seed_lft && !declares_seed_lifetime(self.generics) {
 			syn::Lifetime::new("'seed", Span::call_site()).to_tokens(tokens);
 			<syn::Token![,]>::default().to_tokens(tokens);
 			trailing_or_empty = true;
@@ -370,6 +370,54 @@ impl ToTokens for SeededTypeGenerics<'_> {
 	}
 }
 
+/// Checks if the type already declares the `'seed` lifetime, in which case
+/// it is reused instead of being added by the seeded generics.
+pub fn declares_seed_lifetime(generics: &syn::Generics) -> bool {
+	generics.lifetimes().any(|l| l.lifetime.ident == "seed")
+}
+
+/// Checks if the given type is syntactically an `Option<T>` type.
+pub fn is_option(ty: &syn::Type) -> bool {
+	match ty {
+		syn::Type::Path(path) if path.qself.is_none() => match path.path.segments.last() {
+			Some(segment) => {
+				segment.ident == "Option"
+					&& matches!(
+						&segment.arguments,
+						syn::PathArguments::AngleBracketed(args) if args.args.len() == 1
+					)
+			}
+			None => false,
+		},
+		syn::Type::Group(group) => is_option(&group.elem),
+		syn::Type::Paren(paren) => is_option(&paren.elem),
+		_ => false,
+	}
+}
+
+/// Phantom tuple mentioning the given generic parameters, so they can be
+/// used in the definition of a helper type even when they do not appear in any
+/// of its fields.
+pub struct PhantomParams<'a>(pub &'a [syn::GenericParam]);
+
+impl ToTokens for PhantomParams<'_> {
+	fn to_tokens(&self, tokens: &mut TokenStream) {
+		let items = self.0.iter().filter_map(|p| match p {
+			syn::GenericParam::Lifetime(def) => {
+				let lifetime = &def.lifetime;
+				Some(quote::quote! { &#lifetime () })
+			}
+			syn::GenericParam::Type(param) => {
+				let ident = &param.ident;
+				Some(quote::quote! { fn() -> *const #ident })
+			}
+			syn::GenericParam::Const(_) => None,
+		});
+
+		tokens.extend(quote::quote! { ( #(#items,)* ) })
+	}
+}
+
 pub(crate) struct TokensOrDefault<'a, T: 'a>(pub &'a Option<T>);
 
 impl<T> ToTokens for TokensOrDefault<'_, T>
@@ -432,6 +480,10 @@ where
 	type Item = Result<U, E>;
 
 	fn next(&mut self) -> Option<Self::Item> {
-		(self.f)(self.inner.next()?).transpose()
+		loop {
+			if let Some(item) = (self.f)(self.inner.next()?).transpose() {
+				break Some(item);
+			}
+		}
 	}
 }
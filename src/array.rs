@@ -0,0 +1,118 @@
+//! Seeded (de)serialization of arrays `[T; N]` where `T` requires a seed.
+//!
+//! Just like with `serde`, arrays are (de)serialized as tuples.
+use crate::{
+	de::{Borrowed, Exclusive, SeedFactory, Shared},
+	ser::{SeededMut, SeededRef},
+	DeserializeSeeded, DeserializeSeededMut, DeserializeSeededRef, SerializeSeeded,
+	SerializeSeededMut,
+};
+use serde::{ser::SerializeTuple, Deserializer, Serializer};
+use std::marker::PhantomData;
+
+pub fn serialize_seeded<T, Q, S, const N: usize>(
+	value: &[T; N],
+	seed: &Q,
+	serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	Q: ?Sized,
+	T: SerializeSeeded<Q>,
+	S: Serializer,
+{
+	let mut s = serializer.serialize_tuple(N)?;
+
+	for item in value {
+		s.serialize_element(&SeededRef::new(seed, item))?;
+	}
+
+	s.end()
+}
+
+pub fn serialize_seeded_mut<T, Q, S, const N: usize>(
+	value: &[T; N],
+	seed: &mut Q,
+	serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	Q: ?Sized,
+	T: SerializeSeededMut<Q>,
+	S: Serializer,
+{
+	let mut s = serializer.serialize_tuple(N)?;
+
+	for item in value {
+		s.serialize_element(&SeededMut::new(seed, item))?;
+	}
+
+	s.end()
+}
+
+pub fn deserialize_seeded<'de, T, Q, D, const N: usize>(
+	seed: &Q,
+	deserializer: D,
+) -> Result<[T; N], D::Error>
+where
+	Q: ?Sized,
+	T: DeserializeSeeded<'de, Q>,
+	D: Deserializer<'de>,
+{
+	deserializer.deserialize_tuple(N, Visitor(Shared(seed), PhantomData))
+}
+
+pub fn deserialize_seeded_mut<'de, T, Q, D, const N: usize>(
+	seed: &mut Q,
+	deserializer: D,
+) -> Result<[T; N], D::Error>
+where
+	Q: ?Sized,
+	T: DeserializeSeededMut<'de, Q>,
+	D: Deserializer<'de>,
+{
+	deserializer.deserialize_tuple(N, Visitor(Exclusive(seed), PhantomData))
+}
+
+pub fn deserialize_seeded_ref<'de, 'seed, T, Q, D, const N: usize>(
+	seed: &'seed Q,
+	deserializer: D,
+) -> Result<[T; N], D::Error>
+where
+	Q: ?Sized,
+	T: DeserializeSeededRef<'de, 'seed, Q>,
+	D: Deserializer<'de>,
+{
+	deserializer.deserialize_tuple(N, Visitor(Borrowed(seed), PhantomData))
+}
+
+/// Array visitor, deserializing each item with the seeds of `F`.
+struct Visitor<F, T, const N: usize>(F, PhantomData<T>);
+
+impl<'de, F, T, const N: usize> ::serde::de::Visitor<'de> for Visitor<F, T, N>
+where
+	F: SeedFactory<'de, T>,
+{
+	type Value = [T; N];
+
+	fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(formatter, "an array of length {N}")
+	}
+
+	fn visit_seq<A>(mut self, mut seq: A) -> Result<Self::Value, A::Error>
+	where
+		A: serde::de::SeqAccess<'de>,
+	{
+		let mut result = Vec::with_capacity(N);
+
+		for i in 0..N {
+			match seq.next_element_seed(self.0.seed())? {
+				Some(item) => result.push(item),
+				None => return Err(serde::de::Error::invalid_length(i, &self)),
+			}
+		}
+
+		match result.try_into() {
+			Ok(array) => Ok(array),
+			Err(_) => unreachable!(),
+		}
+	}
+}
@@ -0,0 +1,72 @@
+//! Seeded (de)serialization of `Box<T>` where `T` requires a seed.
+//!
+//! ```ignore
+//! #[derive(SerializeSeeded, DeserializeSeeded)]
+//! #[seeded(serde(seed(Seed)))]
+//! enum Expr {
+//!   Literal(u32),
+//!   Neg(#[seeded(with(serde_seeded::boxed))] Box<Expr>)
+//! }
+//! ```
+use crate::{
+	DeserializeSeeded, DeserializeSeededMut, DeserializeSeededRef, SerializeSeeded,
+	SerializeSeededMut,
+};
+use serde::{Deserializer, Serializer};
+
+#[allow(clippy::borrowed_box)]
+pub fn serialize_seeded<T, Q, S>(value: &Box<T>, seed: &Q, serializer: S) -> Result<S::Ok, S::Error>
+where
+	Q: ?Sized,
+	T: ?Sized + SerializeSeeded<Q>,
+	S: Serializer,
+{
+	T::serialize_seeded(value, seed, serializer)
+}
+
+#[allow(clippy::borrowed_box)]
+pub fn serialize_seeded_mut<T, Q, S>(
+	value: &Box<T>,
+	seed: &mut Q,
+	serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	Q: ?Sized,
+	T: ?Sized + SerializeSeededMut<Q>,
+	S: Serializer,
+{
+	T::serialize_seeded_mut(value, seed, serializer)
+}
+
+pub fn deserialize_seeded<'de, T, Q, D>(seed: &Q, deserializer: D) -> Result<Box<T>, D::Error>
+where
+	Q: ?Sized,
+	T: DeserializeSeeded<'de, Q>,
+	D: Deserializer<'de>,
+{
+	T::deserialize_seeded(seed, deserializer).map(Box::new)
+}
+
+pub fn deserialize_seeded_mut<'de, T, Q, D>(
+	seed: &mut Q,
+	deserializer: D,
+) -> Result<Box<T>, D::Error>
+where
+	Q: ?Sized,
+	T: DeserializeSeededMut<'de, Q>,
+	D: Deserializer<'de>,
+{
+	T::deserialize_seeded_mut(seed, deserializer).map(Box::new)
+}
+
+pub fn deserialize_seeded_ref<'de, 'seed, T, Q, D>(
+	seed: &'seed Q,
+	deserializer: D,
+) -> Result<Box<T>, D::Error>
+where
+	Q: ?Sized,
+	T: DeserializeSeededRef<'de, 'seed, Q>,
+	D: Deserializer<'de>,
+{
+	T::deserialize_seeded_ref(seed, deserializer).map(Box::new)
+}
@@ -0,0 +1,109 @@
+//! Heterogeneous seed context, holding independent seed components.
+//!
+//! Seeded types may require components from their seed instead of a whole
+//! seed type, in which case they can be (de)serialized with any seed
+//! providing these components, such as a [`SeedContext`].
+//!
+//! ```ignore
+//! #[derive(SerializeSeeded, DeserializeSeeded)]
+//! #[seeded(serde(seed(interner: Interner, units: UnitRegistry)))]
+//! struct Measurement {
+//!   #[seeded(seed = interner)]
+//!   name: Symbol,
+//!   #[seeded(seed = units)]
+//!   unit: Unit
+//! }
+//!
+//! let context = SeedContext::new()
+//!   .with(Interner::default())
+//!   .with(UnitRegistry::default());
+//! ```
+use std::{
+	any::{Any, TypeId},
+	collections::HashMap,
+};
+
+/// Seed providing a component of type `T`.
+///
+/// Seed structs composed of several components can implement this trait for
+/// each of them, to be used in place of a [`SeedContext`].
+pub trait Provide<T: ?Sized> {
+	/// Returns the provided component, if any.
+	///
+	/// A missing component makes the (de)serialization of the types requiring
+	/// it fail with a custom error.
+	fn provide(&self) -> Option<&T>;
+}
+
+/// Type-map seed, holding at most one component of each type.
+///
+/// Components must be `Send + Sync` so that the context can be shared
+/// between threads.
+#[derive(Default)]
+pub struct SeedContext {
+	components: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl SeedContext {
+	/// Creates a new empty context.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds the given component to the context, replacing any component of
+	/// the same type.
+	pub fn with<T: Any + Send + Sync>(mut self, component: T) -> Self {
+		self.insert(component);
+		self
+	}
+
+	/// Inserts the given component, returning the previous component of the
+	/// same type, if any.
+	pub fn insert<T: Any + Send + Sync>(&mut self, component: T) -> Option<T> {
+		self.components
+			.insert(TypeId::of::<T>(), Box::new(component))
+			.map(|c| *c.downcast().unwrap())
+	}
+
+	/// Removes and returns the component of type `T`, if any.
+	pub fn remove<T: Any>(&mut self) -> Option<T> {
+		self.components
+			.remove(&TypeId::of::<T>())
+			.map(|c| *c.downcast().unwrap())
+	}
+
+	/// Checks if the context holds a component of type `T`.
+	pub fn contains<T: Any>(&self) -> bool {
+		self.components.contains_key(&TypeId::of::<T>())
+	}
+
+	/// Returns the component of type `T`, if any.
+	pub fn get<T: Any>(&self) -> Option<&T> {
+		self.components
+			.get(&TypeId::of::<T>())
+			.map(|c| c.downcast_ref().unwrap())
+	}
+
+	/// Returns a mutable reference to the component of type `T`, if any.
+	pub fn get_mut<T: Any>(&mut self) -> Option<&mut T> {
+		self.components
+			.get_mut(&TypeId::of::<T>())
+			.map(|c| c.downcast_mut().unwrap())
+	}
+
+	/// Returns the number of components in the context.
+	pub fn len(&self) -> usize {
+		self.components.len()
+	}
+
+	/// Checks if the context has no components.
+	pub fn is_empty(&self) -> bool {
+		self.components.is_empty()
+	}
+}
+
+impl<T: Any> Provide<T> for SeedContext {
+	fn provide(&self) -> Option<&T> {
+		self.get()
+	}
+}
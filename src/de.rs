@@ -52,6 +52,11 @@ pub trait DeserializeSeeded<'de, Q: ?Sized>: Sized {
 
 /// Any type that can be deserialized without that seed (meaning they implement [`serde::Deserialize`]),
 /// automatically implement [`DeserializeSeeded`].
+///
+/// Containers of values requiring a seed (such as `Vec<T>` or `Option<T>`)
+/// can be deserialized using the [`seq`](crate::seq),
+/// [`option`](crate::option), [`map`](crate::map), etc. modules with the
+/// `#[seeded(with(...))]` attribute.
 impl<'de, Q, T> DeserializeSeeded<'de, Q> for T
 where
 	Q: ?Sized,
@@ -64,3 +69,200 @@ where
 		T::deserialize(deserializer)
 	}
 }
+
+/// Seed deserializing any `T` implementing `DeserializeSeededMut<Q>`.
+///
+/// This type implements [`DeserializeSeed`] when `T` implements
+/// [`DeserializeSeededMut<Q>`].
+pub struct SeedMut<'a, Q: ?Sized, T> {
+	seed: &'a mut Q,
+	t: core::marker::PhantomData<T>,
+}
+
+impl<'a, Q: ?Sized, T> SeedMut<'a, Q, T> {
+	/// Creates a new deserializing seed.
+	pub fn new(seed: &'a mut Q) -> Self {
+		Self {
+			seed,
+			t: core::marker::PhantomData,
+		}
+	}
+}
+
+impl<'de, Q, T> DeserializeSeed<'de> for SeedMut<'_, Q, T>
+where
+	Q: ?Sized,
+	T: DeserializeSeededMut<'de, Q>,
+{
+	type Value = T;
+
+	fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		T::deserialize_seeded_mut(self.seed, deserializer)
+	}
+}
+
+/// A data structure that can be deserialized with a mutable seed of type `Q`.
+///
+/// The seed is passed to each deserialized value in document order, allowing
+/// it to record what has been read so far (interned symbols, definitions,
+/// etc.).
+pub trait DeserializeSeededMut<'de, Q: ?Sized>: Sized {
+	/// Deserializes `Self` using the given mutable seed and deserializer.
+	fn deserialize_seeded_mut<D>(seed: &mut Q, deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>;
+}
+
+/// Any type that can be deserialized without that seed (meaning they implement [`serde::Deserialize`]),
+/// automatically implement [`DeserializeSeededMut`].
+impl<'de, Q, T> DeserializeSeededMut<'de, Q> for T
+where
+	Q: ?Sized,
+	T: serde::Deserialize<'de>,
+{
+	fn deserialize_seeded_mut<D>(_seed: &mut Q, deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		T::deserialize(deserializer)
+	}
+}
+
+/// Seed deserializing any `T` implementing `DeserializeSeededRef<'seed, Q>`,
+/// whose value may borrow from the seed.
+///
+/// This type implements [`DeserializeSeed`] when `T` implements
+/// [`DeserializeSeededRef<'seed, Q>`].
+pub struct SeedRef<'seed, Q: ?Sized, T> {
+	seed: &'seed Q,
+	t: core::marker::PhantomData<T>,
+}
+
+impl<'seed, Q: ?Sized, T> SeedRef<'seed, Q, T> {
+	/// Creates a new deserializing seed.
+	pub fn new(seed: &'seed Q) -> Self {
+		Self {
+			seed,
+			t: core::marker::PhantomData,
+		}
+	}
+}
+
+impl<Q: ?Sized, T> Clone for SeedRef<'_, Q, T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<Q: ?Sized, T> Copy for SeedRef<'_, Q, T> {}
+
+impl<'de, 'seed, Q, T> DeserializeSeed<'de> for SeedRef<'seed, Q, T>
+where
+	Q: ?Sized,
+	T: DeserializeSeededRef<'de, 'seed, Q>,
+{
+	type Value = T;
+
+	fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		T::deserialize_seeded_ref(self.seed, deserializer)
+	}
+}
+
+/// A data structure that can be deserialized with a seed of type `Q`,
+/// borrowing from the seed for the `'seed` lifetime.
+///
+/// This allows deserializing references resolved from the seed, such as
+/// `&'seed Node` references to nodes of an arena held by the seed.
+pub trait DeserializeSeededRef<'de, 'seed, Q: ?Sized>: Sized {
+	/// Deserializes `Self` using the given seed and deserializer.
+	fn deserialize_seeded_ref<D>(seed: &'seed Q, deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>;
+}
+
+/// Any type that can be deserialized without that seed (meaning they implement [`serde::Deserialize`]),
+/// automatically implement [`DeserializeSeededRef`].
+impl<'de, Q, T> DeserializeSeededRef<'de, '_, Q> for T
+where
+	Q: ?Sized,
+	T: serde::Deserialize<'de>,
+{
+	fn deserialize_seeded_ref<D>(_seed: &Q, deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		T::deserialize(deserializer)
+	}
+}
+
+/// Source of a fresh [`DeserializeSeed`] for each item of a container, used
+/// by the `with` modules to share a single visitor between the shared,
+/// mutable and borrowed seed flavors.
+pub(crate) trait SeedFactory<'de, T> {
+	type Seed<'s>: DeserializeSeed<'de, Value = T>
+	where
+		Self: 's;
+
+	/// Returns the seed for the next item.
+	fn seed(&mut self) -> Self::Seed<'_>;
+}
+
+/// Shared seed, producing [`Seed`]s.
+pub(crate) struct Shared<'a, Q: ?Sized>(pub &'a Q);
+
+impl<'de, 'a, Q, T> SeedFactory<'de, T> for Shared<'a, Q>
+where
+	Q: ?Sized,
+	T: DeserializeSeeded<'de, Q>,
+{
+	type Seed<'s>
+		= Seed<'a, Q, T>
+	where
+		Self: 's;
+
+	fn seed(&mut self) -> Self::Seed<'_> {
+		Seed::new(self.0)
+	}
+}
+
+/// Mutable seed, producing [`SeedMut`]s reborrowing it for each item.
+pub(crate) struct Exclusive<'a, Q: ?Sized>(pub &'a mut Q);
+
+impl<'de, Q, T> SeedFactory<'de, T> for Exclusive<'_, Q>
+where
+	Q: ?Sized,
+	T: DeserializeSeededMut<'de, Q>,
+{
+	type Seed<'s>
+		= SeedMut<'s, Q, T>
+	where
+		Self: 's;
+
+	fn seed(&mut self) -> Self::Seed<'_> {
+		SeedMut::new(self.0)
+	}
+}
+
+/// Seed borrowed by the items, producing [`SeedRef`]s.
+pub(crate) struct Borrowed<'seed, Q: ?Sized>(pub &'seed Q);
+
+impl<'de, 'seed, Q, T> SeedFactory<'de, T> for Borrowed<'seed, Q>
+where
+	Q: ?Sized,
+	T: DeserializeSeededRef<'de, 'seed, Q>,
+{
+	type Seed<'s>
+		= SeedRef<'seed, Q, T>
+	where
+		Self: 's;
+
+	fn seed(&mut self) -> Self::Seed<'_> {
+		SeedRef::new(self.0)
+	}
+}
@@ -1,28 +1,72 @@
 //! This library provides types, traits and derive macros to deal with seeded
 //! serialization/deserialization with serde.
 //! - A `SerializeSeeded` trait and derive macro to serialize types with a seed.
-//! - A `Seeded<Q, T>` type that implements `Serialize` calling
-//!   `T::serialize_seeded` with a seed `Q`.
+//! - A `Seeded<Q, T>` type (or `SeededRef<Q, T>` for borrowed values) that
+//!   implements `Serialize` calling `T::serialize_seeded` with a seed `Q`.
 //! - A `DeserializeSeeded` trait and derive macro to deserialize types with a
 //!   seed.
 //! - A `Seed<Q, T>` type implementing `DeserializeSeed` calling
 //!   `T::deserialize_seeded` with a seed `Q`.
+//! - A `SerializeSeededMut` trait and `SeededMut<Q, T>` type to serialize
+//!   types with a mutable seed, derived with `#[seeded(ser_mut(...))]`.
+//! - A `DeserializeSeededMut` trait and `SeedMut<Q, T>` type to deserialize
+//!   types with a mutable seed, derived with `#[seeded(de_mut(...))]`.
+//! - A `DeserializeSeededRef` trait and `SeedRef<Q, T>` type to deserialize
+//!   types borrowing from the seed, derived with `#[seeded(de_ref(...))]`.
+//! - A `SeedContext` type-map seed, composing independent seed components
+//!   required with `#[seeded(serde(seed(name: Component)))]`.
+//!
+//! Any type implementing `Serialize` (resp. `Deserialize`) automatically
+//! implements `SerializeSeeded` (resp. `DeserializeSeeded`) for any seed.
+//! Containers of seeded values are handled with the `option`, `boxed`, `seq`,
+//! `array` and `map` modules through the `#[seeded(with(...))]` attribute, on
+//! both the serialization and deserialization sides. Top-level containers are
+//! serialized as collections of `SeededRef` items, and deserialized by calling the
+//! module functions directly.
+//! Regular `serde` adapters are used through `#[seeded(with_unseeded(...))]`,
+//! `serialize_with` and `deserialize_with`.
 //!
 //! See the `tests` folder to find some examples.
 #[cfg(feature = "derive")]
 pub use serde_seeded_derive::{DeserializeSeeded, SerializeSeeded};
 
 pub mod ser;
-pub use ser::SerializeSeeded;
+pub use ser::{SerializeSeeded, SerializeSeededMut};
 
 pub mod de;
-pub use de::DeserializeSeeded;
+pub use de::{DeserializeSeeded, DeserializeSeededMut, DeserializeSeededRef};
+
+pub mod context;
+pub use context::SeedContext;
+
+pub mod array;
+pub mod boxed;
+pub mod map;
+pub mod option;
+pub mod seq;
+
+#[doc(hidden)]
+pub mod private;
 
 pub mod unseeded {
 	use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 	pub fn serialize_seeded<T, Q, S>(value: &T, _seed: &Q, serializer: S) -> Result<S::Ok, S::Error>
 	where
+		Q: ?Sized,
+		T: Serialize,
+		S: Serializer,
+	{
+		value.serialize(serializer)
+	}
+
+	pub fn serialize_seeded_mut<T, Q, S>(
+		value: &T,
+		_seed: &mut Q,
+		serializer: S,
+	) -> Result<S::Ok, S::Error>
+	where
+		Q: ?Sized,
 		T: Serialize,
 		S: Serializer,
 	{
@@ -31,6 +75,28 @@ pub mod unseeded {
 
 	pub fn deserialize_seeded<'de, T, Q, D>(_seed: &Q, deserializer: D) -> Result<T, D::Error>
 	where
+		Q: ?Sized,
+		T: Deserialize<'de>,
+		D: Deserializer<'de>,
+	{
+		T::deserialize(deserializer)
+	}
+
+	pub fn deserialize_seeded_mut<'de, T, Q, D>(
+		_seed: &mut Q,
+		deserializer: D,
+	) -> Result<T, D::Error>
+	where
+		Q: ?Sized,
+		T: Deserialize<'de>,
+		D: Deserializer<'de>,
+	{
+		T::deserialize(deserializer)
+	}
+
+	pub fn deserialize_seeded_ref<'de, T, Q, D>(_seed: &Q, deserializer: D) -> Result<T, D::Error>
+	where
+		Q: ?Sized,
 		T: Deserialize<'de>,
 		D: Deserializer<'de>,
 	{
@@ -39,7 +105,7 @@ pub mod unseeded {
 }
 
 pub mod unseeded_btreemap_key {
-	use crate::{de::Seed, ser::Seeded, DeserializeSeeded, SerializeSeeded};
+	use crate::{de::Seed, ser::SeededRef, DeserializeSeeded, SerializeSeeded};
 	use serde::{ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
 	use std::{collections::BTreeMap, marker::PhantomData};
 
@@ -49,6 +115,7 @@ pub mod unseeded_btreemap_key {
 		serializer: S,
 	) -> Result<S::Ok, S::Error>
 	where
+		Q: ?Sized,
 		K: Serialize,
 		V: SerializeSeeded<Q>,
 		S: Serializer,
@@ -56,7 +123,7 @@ pub mod unseeded_btreemap_key {
 		let mut s = serializer.serialize_map(Some(value.len()))?;
 
 		for (key, value) in value {
-			s.serialize_entry(key, &Seeded::new(seed, value))?;
+			s.serialize_entry(key, &SeededRef::new(seed, value))?;
 		}
 
 		s.end()
@@ -67,14 +134,16 @@ pub mod unseeded_btreemap_key {
 		deserializer: D,
 	) -> Result<BTreeMap<K, V>, D::Error>
 	where
+		Q: ?Sized,
 		K: Ord + Deserialize<'de>,
 		V: DeserializeSeeded<'de, Q>,
 		D: Deserializer<'de>,
 	{
-		struct Visitor<'seed, Q, K, V>(&'seed Q, PhantomData<BTreeMap<K, V>>);
+		struct Visitor<'seed, Q: ?Sized, K, V>(&'seed Q, PhantomData<BTreeMap<K, V>>);
 
 		impl<'de, Q, K, V> ::serde::de::Visitor<'de> for Visitor<'_, Q, K, V>
 		where
+			Q: ?Sized,
 			K: Ord + Deserialize<'de>,
 			V: DeserializeSeeded<'de, Q>,
 		{
@@ -104,7 +173,7 @@ pub mod unseeded_btreemap_key {
 }
 
 pub mod unseeded_hashmap_key {
-	use crate::{de::Seed, ser::Seeded, DeserializeSeeded, SerializeSeeded};
+	use crate::{de::Seed, ser::SeededRef, DeserializeSeeded, SerializeSeeded};
 	use serde::{ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
 	use std::{collections::HashMap, hash::Hash, marker::PhantomData};
 
@@ -114,6 +183,7 @@ pub mod unseeded_hashmap_key {
 		serializer: S,
 	) -> Result<S::Ok, S::Error>
 	where
+		Q: ?Sized,
 		K: Serialize,
 		V: SerializeSeeded<Q>,
 		S: Serializer,
@@ -121,7 +191,7 @@ pub mod unseeded_hashmap_key {
 		let mut s = serializer.serialize_map(Some(value.len()))?;
 
 		for (key, value) in value {
-			s.serialize_entry(key, &Seeded::new(seed, value))?;
+			s.serialize_entry(key, &SeededRef::new(seed, value))?;
 		}
 
 		s.end()
@@ -132,14 +202,16 @@ pub mod unseeded_hashmap_key {
 		deserializer: D,
 	) -> Result<HashMap<K, V>, D::Error>
 	where
+		Q: ?Sized,
 		K: Eq + Hash + Deserialize<'de>,
 		V: DeserializeSeeded<'de, Q>,
 		D: Deserializer<'de>,
 	{
-		struct Visitor<'seed, Q, K, V>(&'seed Q, PhantomData<HashMap<K, V>>);
+		struct Visitor<'seed, Q: ?Sized, K, V>(&'seed Q, PhantomData<HashMap<K, V>>);
 
 		impl<'de, Q, K, V> ::serde::de::Visitor<'de> for Visitor<'_, Q, K, V>
 		where
+			Q: ?Sized,
 			K: Eq + Hash + Deserialize<'de>,
 			V: DeserializeSeeded<'de, Q>,
 		{
@@ -0,0 +1,136 @@
+//! Seeded (de)serialization of maps (`BTreeMap<K, V>`, `HashMap<K, V>`, etc.)
+//! where both keys and values require a seed.
+//!
+//! If only the values require a seed, see the
+//! [`unseeded_btreemap_key`](crate::unseeded_btreemap_key) and
+//! [`unseeded_hashmap_key`](crate::unseeded_hashmap_key) modules.
+use crate::{
+	de::{Borrowed, Exclusive, SeedFactory, Shared},
+	ser::{SeededMut, SeededRef},
+	DeserializeSeeded, DeserializeSeededMut, DeserializeSeededRef, SerializeSeeded,
+	SerializeSeededMut,
+};
+use serde::{ser::SerializeMap, Deserializer, Serializer};
+use std::marker::PhantomData;
+
+pub fn serialize_seeded<M, K, V, Q, S>(
+	value: &M,
+	seed: &Q,
+	serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	Q: ?Sized,
+	M: ?Sized,
+	for<'a> &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+	K: SerializeSeeded<Q>,
+	V: SerializeSeeded<Q>,
+	S: Serializer,
+{
+	serializer.collect_map(
+		value
+			.into_iter()
+			.map(|(key, value)| (SeededRef::new(seed, key), SeededRef::new(seed, value))),
+	)
+}
+
+pub fn serialize_seeded_mut<M, K, V, Q, S>(
+	value: &M,
+	seed: &mut Q,
+	serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	Q: ?Sized,
+	M: ?Sized,
+	for<'a> &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+	K: SerializeSeededMut<Q>,
+	V: SerializeSeededMut<Q>,
+	S: Serializer,
+{
+	let entries = value.into_iter();
+	let len = match entries.size_hint() {
+		(min, Some(max)) if min == max => Some(min),
+		_ => None,
+	};
+
+	let mut s = serializer.serialize_map(len)?;
+
+	for (key, value) in entries {
+		s.serialize_key(&SeededMut::new(seed, key))?;
+		s.serialize_value(&SeededMut::new(seed, value))?;
+	}
+
+	s.end()
+}
+
+pub fn deserialize_seeded<'de, M, K, V, Q, D>(seed: &Q, deserializer: D) -> Result<M, D::Error>
+where
+	M: FromIterator<(K, V)>,
+	Q: ?Sized,
+	K: DeserializeSeeded<'de, Q>,
+	V: DeserializeSeeded<'de, Q>,
+	D: Deserializer<'de>,
+{
+	deserializer.deserialize_map(Visitor(Shared(seed), PhantomData))
+}
+
+pub fn deserialize_seeded_mut<'de, M, K, V, Q, D>(
+	seed: &mut Q,
+	deserializer: D,
+) -> Result<M, D::Error>
+where
+	M: FromIterator<(K, V)>,
+	Q: ?Sized,
+	K: DeserializeSeededMut<'de, Q>,
+	V: DeserializeSeededMut<'de, Q>,
+	D: Deserializer<'de>,
+{
+	deserializer.deserialize_map(Visitor(Exclusive(seed), PhantomData))
+}
+
+pub fn deserialize_seeded_ref<'de, 'seed, M, K, V, Q, D>(
+	seed: &'seed Q,
+	deserializer: D,
+) -> Result<M, D::Error>
+where
+	M: FromIterator<(K, V)>,
+	Q: ?Sized,
+	K: DeserializeSeededRef<'de, 'seed, Q>,
+	V: DeserializeSeededRef<'de, 'seed, Q>,
+	D: Deserializer<'de>,
+{
+	deserializer.deserialize_map(Visitor(Borrowed(seed), PhantomData))
+}
+
+/// Map visitor collecting entries deserialized with the seeds of `F`
+/// directly into `M`.
+struct Visitor<F, K, V, M>(F, PhantomData<(K, V, M)>);
+
+impl<'de, F, K, V, M> ::serde::de::Visitor<'de> for Visitor<F, K, V, M>
+where
+	F: SeedFactory<'de, K> + SeedFactory<'de, V>,
+	M: FromIterator<(K, V)>,
+{
+	type Value = M;
+
+	fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(formatter, "a map")
+	}
+
+	fn visit_map<A>(mut self, mut map: A) -> Result<Self::Value, A::Error>
+	where
+		A: serde::de::MapAccess<'de>,
+	{
+		std::iter::from_fn(|| {
+			let key = map.next_key_seed(SeedFactory::<K>::seed(&mut self.0));
+			match key {
+				Ok(Some(key)) => Some(
+					map.next_value_seed(SeedFactory::<V>::seed(&mut self.0))
+						.map(|value| (key, value)),
+				),
+				Ok(None) => None,
+				Err(e) => Some(Err(e)),
+			}
+		})
+		.collect()
+	}
+}
@@ -0,0 +1,118 @@
+//! Seeded (de)serialization of `Option<T>` where `T` requires a seed.
+//!
+//! ```ignore
+//! #[derive(SerializeSeeded, DeserializeSeeded)]
+//! #[seeded(serde(seed(Seed)))]
+//! struct Foo {
+//!   #[seeded(with(serde_seeded::option))]
+//!   bar: Option<Bar>
+//! }
+//! ```
+use crate::{
+	de::{Borrowed, Exclusive, SeedFactory, Shared},
+	ser::{SeededMut, SeededRef},
+	DeserializeSeeded, DeserializeSeededMut, DeserializeSeededRef, SerializeSeeded,
+	SerializeSeededMut,
+};
+use serde::{de::DeserializeSeed, Deserializer, Serializer};
+use std::marker::PhantomData;
+
+pub fn serialize_seeded<T, Q, S>(
+	value: &Option<T>,
+	seed: &Q,
+	serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	Q: ?Sized,
+	T: SerializeSeeded<Q>,
+	S: Serializer,
+{
+	match value {
+		Some(t) => serializer.serialize_some(&SeededRef::new(seed, t)),
+		None => serializer.serialize_none(),
+	}
+}
+
+pub fn serialize_seeded_mut<T, Q, S>(
+	value: &Option<T>,
+	seed: &mut Q,
+	serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	Q: ?Sized,
+	T: SerializeSeededMut<Q>,
+	S: Serializer,
+{
+	match value {
+		Some(t) => serializer.serialize_some(&SeededMut::new(seed, t)),
+		None => serializer.serialize_none(),
+	}
+}
+
+pub fn deserialize_seeded<'de, T, Q, D>(seed: &Q, deserializer: D) -> Result<Option<T>, D::Error>
+where
+	Q: ?Sized,
+	T: DeserializeSeeded<'de, Q>,
+	D: Deserializer<'de>,
+{
+	deserializer.deserialize_option(Visitor(Shared(seed), PhantomData))
+}
+
+pub fn deserialize_seeded_mut<'de, T, Q, D>(
+	seed: &mut Q,
+	deserializer: D,
+) -> Result<Option<T>, D::Error>
+where
+	Q: ?Sized,
+	T: DeserializeSeededMut<'de, Q>,
+	D: Deserializer<'de>,
+{
+	deserializer.deserialize_option(Visitor(Exclusive(seed), PhantomData))
+}
+
+pub fn deserialize_seeded_ref<'de, 'seed, T, Q, D>(
+	seed: &'seed Q,
+	deserializer: D,
+) -> Result<Option<T>, D::Error>
+where
+	Q: ?Sized,
+	T: DeserializeSeededRef<'de, 'seed, Q>,
+	D: Deserializer<'de>,
+{
+	deserializer.deserialize_option(Visitor(Borrowed(seed), PhantomData))
+}
+
+/// Optional value visitor, deserializing the value with the seed of `F`.
+struct Visitor<F, T>(F, PhantomData<T>);
+
+impl<'de, F, T> ::serde::de::Visitor<'de> for Visitor<F, T>
+where
+	F: SeedFactory<'de, T>,
+{
+	type Value = Option<T>;
+
+	fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(formatter, "an optional value")
+	}
+
+	fn visit_none<E>(self) -> Result<Self::Value, E>
+	where
+		E: serde::de::Error,
+	{
+		Ok(None)
+	}
+
+	fn visit_unit<E>(self) -> Result<Self::Value, E>
+	where
+		E: serde::de::Error,
+	{
+		Ok(None)
+	}
+
+	fn visit_some<D>(mut self, deserializer: D) -> Result<Self::Value, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		self.0.seed().deserialize(deserializer).map(Some)
+	}
+}
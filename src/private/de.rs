@@ -0,0 +1,1120 @@
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+	de::{
+		value::{MapDeserializer, SeqAccessDeserializer, SeqDeserializer},
+		DeserializeSeed, EnumAccess, Error, IgnoredAny, IntoDeserializer, MapAccess, SeqAccess,
+		Unexpected, VariantAccess, Visitor,
+	},
+	forward_to_deserialize_any, Deserialize, Deserializer,
+};
+
+/// Buffered value, used when the seed cannot be passed to the deserializer
+/// right away (for instance because an enum tag has not been read yet).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Content<'de> {
+	Bool(bool),
+	U8(u8),
+	U16(u16),
+	U32(u32),
+	U64(u64),
+	I8(i8),
+	I16(i16),
+	I32(i32),
+	I64(i64),
+	F32(f32),
+	F64(f64),
+	Char(char),
+	String(String),
+	Str(&'de str),
+	ByteBuf(Vec<u8>),
+	Bytes(&'de [u8]),
+	None,
+	Some(Box<Content<'de>>),
+	Unit,
+	Newtype(Box<Content<'de>>),
+	Seq(Vec<Content<'de>>),
+	Map(Vec<(Content<'de>, Content<'de>)>),
+}
+
+impl<'de> Content<'de> {
+	/// Returns the content as a string, if it is one.
+	pub fn as_str(&self) -> Option<&str> {
+		match self {
+			Self::String(s) => Some(s),
+			Self::Str(s) => Some(s),
+			Self::ByteBuf(b) => std::str::from_utf8(b).ok(),
+			Self::Bytes(b) => std::str::from_utf8(b).ok(),
+			_ => None,
+		}
+	}
+
+	fn unexpected(&self) -> Unexpected<'_> {
+		match self {
+			Self::Bool(b) => Unexpected::Bool(*b),
+			Self::U8(n) => Unexpected::Unsigned(*n as u64),
+			Self::U16(n) => Unexpected::Unsigned(*n as u64),
+			Self::U32(n) => Unexpected::Unsigned(*n as u64),
+			Self::U64(n) => Unexpected::Unsigned(*n),
+			Self::I8(n) => Unexpected::Signed(*n as i64),
+			Self::I16(n) => Unexpected::Signed(*n as i64),
+			Self::I32(n) => Unexpected::Signed(*n as i64),
+			Self::I64(n) => Unexpected::Signed(*n),
+			Self::F32(f) => Unexpected::Float(*f as f64),
+			Self::F64(f) => Unexpected::Float(*f),
+			Self::Char(c) => Unexpected::Char(*c),
+			Self::String(s) => Unexpected::Str(s),
+			Self::Str(s) => Unexpected::Str(s),
+			Self::ByteBuf(b) => Unexpected::Bytes(b),
+			Self::Bytes(b) => Unexpected::Bytes(b),
+			Self::None | Self::Some(_) => Unexpected::Option,
+			Self::Unit => Unexpected::Unit,
+			Self::Newtype(_) => Unexpected::NewtypeStruct,
+			Self::Seq(_) => Unexpected::Seq,
+			Self::Map(_) => Unexpected::Map,
+		}
+	}
+}
+
+impl<'de> Deserialize<'de> for Content<'de> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		deserializer.deserialize_any(ContentVisitor)
+	}
+}
+
+struct ContentVisitor;
+
+impl<'de> Visitor<'de> for ContentVisitor {
+	type Value = Content<'de>;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		write!(formatter, "any value")
+	}
+
+	fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+		Ok(Content::Bool(v))
+	}
+
+	fn visit_i8<E>(self, v: i8) -> Result<Self::Value, E> {
+		Ok(Content::I8(v))
+	}
+
+	fn visit_i16<E>(self, v: i16) -> Result<Self::Value, E> {
+		Ok(Content::I16(v))
+	}
+
+	fn visit_i32<E>(self, v: i32) -> Result<Self::Value, E> {
+		Ok(Content::I32(v))
+	}
+
+	fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+		Ok(Content::I64(v))
+	}
+
+	fn visit_u8<E>(self, v: u8) -> Result<Self::Value, E> {
+		Ok(Content::U8(v))
+	}
+
+	fn visit_u16<E>(self, v: u16) -> Result<Self::Value, E> {
+		Ok(Content::U16(v))
+	}
+
+	fn visit_u32<E>(self, v: u32) -> Result<Self::Value, E> {
+		Ok(Content::U32(v))
+	}
+
+	fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+		Ok(Content::U64(v))
+	}
+
+	fn visit_f32<E>(self, v: f32) -> Result<Self::Value, E> {
+		Ok(Content::F32(v))
+	}
+
+	fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+		Ok(Content::F64(v))
+	}
+
+	fn visit_char<E>(self, v: char) -> Result<Self::Value, E> {
+		Ok(Content::Char(v))
+	}
+
+	fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+		Ok(Content::String(v.to_owned()))
+	}
+
+	fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E> {
+		Ok(Content::Str(v))
+	}
+
+	fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+		Ok(Content::String(v))
+	}
+
+	fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> {
+		Ok(Content::ByteBuf(v.to_owned()))
+	}
+
+	fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+		Ok(Content::Bytes(v))
+	}
+
+	fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+		Ok(Content::ByteBuf(v))
+	}
+
+	fn visit_none<E>(self) -> Result<Self::Value, E> {
+		Ok(Content::None)
+	}
+
+	fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		Content::deserialize(deserializer).map(|v| Content::Some(Box::new(v)))
+	}
+
+	fn visit_unit<E>(self) -> Result<Self::Value, E> {
+		Ok(Content::Unit)
+	}
+
+	fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		Content::deserialize(deserializer).map(|v| Content::Newtype(Box::new(v)))
+	}
+
+	fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+	where
+		A: SeqAccess<'de>,
+	{
+		let mut items = Vec::with_capacity(seq.size_hint().unwrap_or_default().min(4096));
+
+		while let Some(item) = seq.next_element()? {
+			items.push(item)
+		}
+
+		Ok(Content::Seq(items))
+	}
+
+	fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+	where
+		A: MapAccess<'de>,
+	{
+		let mut entries = Vec::with_capacity(map.size_hint().unwrap_or_default().min(4096));
+
+		while let Some(entry) = map.next_entry()? {
+			entries.push(entry)
+		}
+
+		Ok(Content::Map(entries))
+	}
+
+	fn visit_enum<A>(self, _data: A) -> Result<Self::Value, A::Error>
+	where
+		A: EnumAccess<'de>,
+	{
+		Err(A::Error::custom(
+			"untagged and internally tagged enums do not support enum input",
+		))
+	}
+}
+
+impl<'de, E: Error> IntoDeserializer<'de, E> for Content<'de> {
+	type Deserializer = ContentDeserializer<'de, E>;
+
+	fn into_deserializer(self) -> Self::Deserializer {
+		ContentDeserializer::new(self)
+	}
+}
+
+/// Deserializer reading a buffered [`Content`].
+pub struct ContentDeserializer<'de, E> {
+	content: Content<'de>,
+	e: PhantomData<E>,
+}
+
+impl<'de, E> ContentDeserializer<'de, E> {
+	pub fn new(content: Content<'de>) -> Self {
+		Self {
+			content,
+			e: PhantomData,
+		}
+	}
+}
+
+impl<'de, E: Error> Deserializer<'de> for ContentDeserializer<'de, E> {
+	type Error = E;
+
+	fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		match self.content {
+			Content::Bool(v) => visitor.visit_bool(v),
+			Content::U8(v) => visitor.visit_u8(v),
+			Content::U16(v) => visitor.visit_u16(v),
+			Content::U32(v) => visitor.visit_u32(v),
+			Content::U64(v) => visitor.visit_u64(v),
+			Content::I8(v) => visitor.visit_i8(v),
+			Content::I16(v) => visitor.visit_i16(v),
+			Content::I32(v) => visitor.visit_i32(v),
+			Content::I64(v) => visitor.visit_i64(v),
+			Content::F32(v) => visitor.visit_f32(v),
+			Content::F64(v) => visitor.visit_f64(v),
+			Content::Char(v) => visitor.visit_char(v),
+			Content::String(v) => visitor.visit_string(v),
+			Content::Str(v) => visitor.visit_borrowed_str(v),
+			Content::ByteBuf(v) => visitor.visit_byte_buf(v),
+			Content::Bytes(v) => visitor.visit_borrowed_bytes(v),
+			Content::None => visitor.visit_none(),
+			Content::Some(v) => visitor.visit_some(ContentDeserializer::new(*v)),
+			Content::Unit => visitor.visit_unit(),
+			Content::Newtype(v) => visitor.visit_newtype_struct(ContentDeserializer::new(*v)),
+			Content::Seq(items) => {
+				let mut seq = SeqDeserializer::new(items.into_iter());
+				let value = visitor.visit_seq(&mut seq)?;
+				seq.end()?;
+				Ok(value)
+			}
+			Content::Map(entries) => {
+				let mut map = MapDeserializer::new(entries.into_iter());
+				let value = visitor.visit_map(&mut map)?;
+				map.end()?;
+				Ok(value)
+			}
+		}
+	}
+
+	fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		match self.content {
+			Content::None | Content::Unit => visitor.visit_none(),
+			Content::Some(v) => visitor.visit_some(ContentDeserializer::new(*v)),
+			_ => visitor.visit_some(self),
+		}
+	}
+
+	fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		match self.content {
+			Content::Newtype(v) => visitor.visit_newtype_struct(ContentDeserializer::new(*v)),
+			_ => visitor.visit_newtype_struct(self),
+		}
+	}
+
+	fn deserialize_enum<V>(
+		self,
+		_name: &'static str,
+		_variants: &'static [&'static str],
+		visitor: V,
+	) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		let (variant, value) = match self.content {
+			Content::Map(entries) => {
+				let mut entries = entries.into_iter();
+				match (entries.next(), entries.next()) {
+					(Some((variant, value)), None) => (variant, Some(value)),
+					_ => return Err(E::invalid_value(Unexpected::Map, &"map with a single key")),
+				}
+			}
+			content @ (Content::String(_) | Content::Str(_)) => (content, None),
+			other => return Err(E::invalid_type(other.unexpected(), &"string or map")),
+		};
+
+		visitor.visit_enum(EnumDeserializer {
+			variant,
+			value,
+			e: PhantomData,
+		})
+	}
+
+	fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		visitor.visit_unit()
+	}
+
+	forward_to_deserialize_any! {
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+		bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+		identifier
+	}
+}
+
+struct EnumDeserializer<'de, E> {
+	variant: Content<'de>,
+	value: Option<Content<'de>>,
+	e: PhantomData<E>,
+}
+
+impl<'de, E: Error> EnumAccess<'de> for EnumDeserializer<'de, E> {
+	type Error = E;
+	type Variant = VariantDeserializer<'de, E>;
+
+	fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), E>
+	where
+		V: DeserializeSeed<'de>,
+	{
+		let variant = seed.deserialize(ContentDeserializer::new(self.variant))?;
+		Ok((
+			variant,
+			VariantDeserializer {
+				value: self.value,
+				e: PhantomData,
+			},
+		))
+	}
+}
+
+struct VariantDeserializer<'de, E> {
+	value: Option<Content<'de>>,
+	e: PhantomData<E>,
+}
+
+impl<'de, E: Error> VariantAccess<'de> for VariantDeserializer<'de, E> {
+	type Error = E;
+
+	fn unit_variant(self) -> Result<(), E> {
+		match self.value {
+			None | Some(Content::Unit) => Ok(()),
+			Some(other) => Err(E::invalid_type(other.unexpected(), &"unit variant")),
+		}
+	}
+
+	fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, E>
+	where
+		T: DeserializeSeed<'de>,
+	{
+		match self.value {
+			Some(value) => seed.deserialize(ContentDeserializer::new(value)),
+			None => Err(E::invalid_type(Unexpected::UnitVariant, &"newtype variant")),
+		}
+	}
+
+	fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		match self.value {
+			Some(value @ Content::Seq(_)) => {
+				ContentDeserializer::new(value).deserialize_any(visitor)
+			}
+			Some(other) => Err(E::invalid_type(other.unexpected(), &"tuple variant")),
+			None => Err(E::invalid_type(Unexpected::UnitVariant, &"tuple variant")),
+		}
+	}
+
+	fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		match self.value {
+			Some(value @ (Content::Seq(_) | Content::Map(_))) => {
+				ContentDeserializer::new(value).deserialize_any(visitor)
+			}
+			Some(other) => Err(E::invalid_type(other.unexpected(), &"struct variant")),
+			None => Err(E::invalid_type(Unexpected::UnitVariant, &"struct variant")),
+		}
+	}
+}
+
+/// Visitor reading the tag of an internally tagged enum, buffering the rest of
+/// the input.
+pub struct TaggedContentVisitor<T> {
+	tag: &'static str,
+	expecting: &'static str,
+	t: PhantomData<T>,
+}
+
+impl<T> TaggedContentVisitor<T> {
+	pub fn new(tag: &'static str, expecting: &'static str) -> Self {
+		Self {
+			tag,
+			expecting,
+			t: PhantomData,
+		}
+	}
+}
+
+impl<'de, T> Visitor<'de> for TaggedContentVisitor<T>
+where
+	T: Deserialize<'de>,
+{
+	type Value = (T, Content<'de>);
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		formatter.write_str(self.expecting)
+	}
+
+	fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+	where
+		A: SeqAccess<'de>,
+	{
+		let tag = seq
+			.next_element()?
+			.ok_or_else(|| A::Error::missing_field(self.tag))?;
+		let rest = Content::deserialize(SeqAccessDeserializer::new(seq))?;
+		Ok((tag, rest))
+	}
+
+	fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+	where
+		A: MapAccess<'de>,
+	{
+		let mut tag = None;
+		let mut entries = Vec::with_capacity(map.size_hint().unwrap_or_default().min(4096));
+
+		while let Some(key) = map.next_key::<Content>()? {
+			if key.as_str() == Some(self.tag) {
+				if tag.is_some() {
+					return Err(A::Error::duplicate_field(self.tag));
+				}
+
+				tag = Some(map.next_value()?);
+			} else {
+				entries.push((key, map.next_value()?));
+			}
+		}
+
+		match tag {
+			Some(tag) => Ok((tag, Content::Map(entries))),
+			None => Err(A::Error::missing_field(self.tag)),
+		}
+	}
+}
+
+/// Field of an adjacently tagged enum.
+pub enum TagOrContentField {
+	Tag,
+	Content,
+	Other,
+}
+
+/// Seed deserializing a [`TagOrContentField`].
+pub struct TagOrContentFieldSeed {
+	tag: &'static str,
+	content: &'static str,
+
+	/// Expected fields, reported when unknown fields are denied.
+	deny_unknown_fields: Option<&'static [&'static str]>,
+}
+
+impl TagOrContentFieldSeed {
+	pub fn new(tag: &'static str, content: &'static str) -> Self {
+		Self {
+			tag,
+			content,
+			deny_unknown_fields: None,
+		}
+	}
+
+	/// Fails on unknown fields instead of returning
+	/// [`TagOrContentField::Other`].
+	pub fn deny_unknown_fields(self, fields: &'static [&'static str]) -> Self {
+		Self {
+			deny_unknown_fields: Some(fields),
+			..self
+		}
+	}
+}
+
+impl<'de> DeserializeSeed<'de> for TagOrContentFieldSeed {
+	type Value = TagOrContentField;
+
+	fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		deserializer.deserialize_identifier(self)
+	}
+}
+
+impl Visitor<'_> for TagOrContentFieldSeed {
+	type Value = TagOrContentField;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		write!(formatter, "{:?} or {:?}", self.tag, self.content)
+	}
+
+	fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+	where
+		E: Error,
+	{
+		match v {
+			0 => Ok(TagOrContentField::Tag),
+			1 => Ok(TagOrContentField::Content),
+			_ if self.deny_unknown_fields.is_some() => Err(E::invalid_value(
+				Unexpected::Unsigned(v),
+				&"field index 0 <= i < 2",
+			)),
+			_ => Ok(TagOrContentField::Other),
+		}
+	}
+
+	fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+	where
+		E: Error,
+	{
+		if v == self.tag {
+			Ok(TagOrContentField::Tag)
+		} else if v == self.content {
+			Ok(TagOrContentField::Content)
+		} else if let Some(fields) = self.deny_unknown_fields {
+			Err(E::unknown_field(v, fields))
+		} else {
+			Ok(TagOrContentField::Other)
+		}
+	}
+
+	fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+	where
+		E: Error,
+	{
+		if v == self.tag.as_bytes() {
+			Ok(TagOrContentField::Tag)
+		} else if v == self.content.as_bytes() {
+			Ok(TagOrContentField::Content)
+		} else if let Some(fields) = self.deny_unknown_fields {
+			match std::str::from_utf8(v) {
+				Ok(v) => Err(E::unknown_field(v, fields)),
+				Err(_) => Err(E::invalid_value(Unexpected::Bytes(v), &self)),
+			}
+		} else {
+			Ok(TagOrContentField::Other)
+		}
+	}
+}
+
+/// Visitor accepting a unit value.
+pub struct UnitVisitor;
+
+impl<'de> Visitor<'de> for UnitVisitor {
+	type Value = ();
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		write!(formatter, "unit")
+	}
+
+	fn visit_unit<E>(self) -> Result<(), E> {
+		Ok(())
+	}
+}
+
+/// Visitor accepting the content of a unit variant.
+///
+/// If `lenient` is true, any sequence or map is also accepted and ignored,
+/// as is needed for internally tagged enums where the rest of the map has
+/// already been buffered.
+pub struct UnitVariantVisitor {
+	pub lenient: bool,
+}
+
+impl<'de> Visitor<'de> for UnitVariantVisitor {
+	type Value = ();
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		write!(formatter, "unit variant")
+	}
+
+	fn visit_unit<E>(self) -> Result<(), E> {
+		Ok(())
+	}
+
+	fn visit_none<E>(self) -> Result<(), E> {
+		Ok(())
+	}
+
+	fn visit_seq<A>(self, mut seq: A) -> Result<(), A::Error>
+	where
+		A: SeqAccess<'de>,
+	{
+		if self.lenient {
+			while seq.next_element::<IgnoredAny>()?.is_some() {}
+			Ok(())
+		} else {
+			Err(A::Error::invalid_type(Unexpected::Seq, &self))
+		}
+	}
+
+	fn visit_map<A>(self, mut map: A) -> Result<(), A::Error>
+	where
+		A: MapAccess<'de>,
+	{
+		if self.lenient {
+			while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+			Ok(())
+		} else {
+			Err(A::Error::invalid_type(Unexpected::Map, &self))
+		}
+	}
+}
+
+/// Variant access reading the content of a variant from a buffered
+/// [`Content`].
+pub struct ContentVariant<'de, E> {
+	content: Content<'de>,
+	lenient: bool,
+	e: PhantomData<E>,
+}
+
+impl<'de, E> ContentVariant<'de, E> {
+	pub fn new(content: Content<'de>) -> Self {
+		Self {
+			content,
+			lenient: false,
+			e: PhantomData,
+		}
+	}
+
+	/// Variant access for the remaining content of an internally tagged
+	/// enum, where unit variants may be followed by any number of ignored
+	/// entries.
+	pub fn internally_tagged(content: Content<'de>) -> Self {
+		Self {
+			content,
+			lenient: true,
+			e: PhantomData,
+		}
+	}
+}
+
+impl<'de, E: Error> VariantAccess<'de> for ContentVariant<'de, E> {
+	type Error = E;
+
+	fn unit_variant(self) -> Result<(), E> {
+		ContentDeserializer::new(self.content).deserialize_any(UnitVariantVisitor {
+			lenient: self.lenient,
+		})
+	}
+
+	fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, E>
+	where
+		T: DeserializeSeed<'de>,
+	{
+		seed.deserialize(ContentDeserializer::new(self.content))
+	}
+
+	fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		ContentDeserializer::new(self.content).deserialize_any(visitor)
+	}
+
+	fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		ContentDeserializer::new(self.content).deserialize_any(visitor)
+	}
+}
+
+/// Variant access reading the content of a variant directly from a
+/// deserializer.
+pub struct DeserializerVariant<D> {
+	deserializer: D,
+	name: &'static str,
+}
+
+impl<D> DeserializerVariant<D> {
+	pub fn new(deserializer: D, name: &'static str) -> Self {
+		Self { deserializer, name }
+	}
+}
+
+impl<'de, D: Deserializer<'de>> VariantAccess<'de> for DeserializerVariant<D> {
+	type Error = D::Error;
+
+	fn unit_variant(self) -> Result<(), D::Error> {
+		self.deserializer
+			.deserialize_unit(UnitVariantVisitor { lenient: false })
+	}
+
+	fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, D::Error>
+	where
+		T: DeserializeSeed<'de>,
+	{
+		seed.deserialize(self.deserializer)
+	}
+
+	fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, D::Error>
+	where
+		V: Visitor<'de>,
+	{
+		self.deserializer.deserialize_tuple(len, visitor)
+	}
+
+	fn struct_variant<V>(
+		self,
+		fields: &'static [&'static str],
+		visitor: V,
+	) -> Result<V::Value, D::Error>
+	where
+		V: Visitor<'de>,
+	{
+		self.deserializer
+			.deserialize_struct(self.name, fields, visitor)
+	}
+}
+
+/// Errors collected while trying each variant of an untagged enum.
+pub struct UntaggedErrors {
+	name: &'static str,
+	errors: Vec<(&'static str, String)>,
+}
+
+impl UntaggedErrors {
+	pub fn new(name: &'static str) -> Self {
+		Self {
+			name,
+			errors: Vec::new(),
+		}
+	}
+
+	pub fn push(&mut self, variant: &'static str, error: impl fmt::Display) {
+		self.errors.push((variant, error.to_string()))
+	}
+
+	pub fn into_error<E: Error>(self) -> E {
+		E::custom(self)
+	}
+}
+
+impl fmt::Display for UntaggedErrors {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"data did not match any variant of untagged enum {}",
+			self.name
+		)?;
+
+		for (i, (variant, error)) in self.errors.iter().enumerate() {
+			let sep = if i == 0 { ": " } else { "; " };
+			write!(f, "{sep}{variant}: {error}")?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Entries of a map not matching any field of a struct with flattened fields.
+pub type FlatEntries<'de> = Vec<Option<(Content<'de>, Content<'de>)>>;
+
+/// Deserializer reading a flattened field from the entries of the
+/// surrounding map that were not matched by any other field.
+///
+/// Structs consume the entries matching their fields, while maps see every
+/// remaining entry.
+pub struct FlatMapDeserializer<'a, 'de, E> {
+	entries: &'a mut FlatEntries<'de>,
+	e: PhantomData<E>,
+}
+
+impl<'a, 'de, E> FlatMapDeserializer<'a, 'de, E> {
+	pub fn new(entries: &'a mut FlatEntries<'de>) -> Self {
+		Self {
+			entries,
+			e: PhantomData,
+		}
+	}
+}
+
+impl<'de, E: Error> Deserializer<'de> for FlatMapDeserializer<'_, 'de, E> {
+	type Error = E;
+
+	fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		self.deserialize_map(visitor)
+	}
+
+	fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		visitor.visit_map(FlatMapAccess {
+			iter: self.entries.iter_mut(),
+			fields: None,
+			pending: None,
+			e: PhantomData,
+		})
+	}
+
+	fn deserialize_struct<V>(
+		self,
+		_name: &'static str,
+		fields: &'static [&'static str],
+		visitor: V,
+	) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		visitor.visit_map(FlatMapAccess {
+			iter: self.entries.iter_mut(),
+			fields: Some(fields),
+			pending: None,
+			e: PhantomData,
+		})
+	}
+
+	fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		visitor.visit_some(self)
+	}
+
+	fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		visitor.visit_newtype_struct(self)
+	}
+
+	fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		visitor.visit_unit()
+	}
+
+	fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		visitor.visit_unit()
+	}
+
+	fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		visitor.visit_unit()
+	}
+
+	fn deserialize_bool<V>(self, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_i8<V>(self, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_i16<V>(self, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_i32<V>(self, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_i64<V>(self, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_u8<V>(self, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_u16<V>(self, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_u32<V>(self, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_u64<V>(self, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_f32<V>(self, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_f64<V>(self, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_char<V>(self, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_str<V>(self, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_string<V>(self, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_bytes<V>(self, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_byte_buf<V>(self, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_seq<V>(self, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_tuple<V>(self, _len: usize, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_tuple_struct<V>(
+		self,
+		_name: &'static str,
+		_len: usize,
+		_visitor: V,
+	) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_enum<V>(
+		self,
+		_name: &'static str,
+		_variants: &'static [&'static str],
+		_visitor: V,
+	) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+
+	fn deserialize_identifier<V>(self, _visitor: V) -> Result<V::Value, E>
+	where
+		V: Visitor<'de>,
+	{
+		Err(flat_map_unsupported())
+	}
+}
+
+fn flat_map_unsupported<E: Error>() -> E {
+	E::custom("can only flatten structs and maps")
+}
+
+struct FlatMapAccess<'a, 'de, E> {
+	iter: std::slice::IterMut<'a, Option<(Content<'de>, Content<'de>)>>,
+	fields: Option<&'static [&'static str]>,
+	pending: Option<Content<'de>>,
+	e: PhantomData<E>,
+}
+
+impl<'de, E: Error> MapAccess<'de> for FlatMapAccess<'_, 'de, E> {
+	type Error = E;
+
+	fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, E>
+	where
+		K: DeserializeSeed<'de>,
+	{
+		for entry in self.iter.by_ref() {
+			match self.fields {
+				Some(fields) => {
+					let matches = entry.as_ref().is_some_and(|(key, _)| {
+						key.as_str().is_some_and(|key| fields.contains(&key))
+					});
+
+					if matches {
+						let (key, value) = entry.take().unwrap();
+						self.pending = Some(value);
+						return seed.deserialize(ContentDeserializer::new(key)).map(Some);
+					}
+				}
+				None => {
+					if let Some((key, value)) = entry {
+						self.pending = Some(value.clone());
+						return seed
+							.deserialize(ContentDeserializer::new(key.clone()))
+							.map(Some);
+					}
+				}
+			}
+		}
+
+		Ok(None)
+	}
+
+	fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, E>
+	where
+		V: DeserializeSeed<'de>,
+	{
+		match self.pending.take() {
+			Some(value) => seed.deserialize(ContentDeserializer::new(value)),
+			None => Err(E::custom("value is missing")),
+		}
+	}
+}
@@ -0,0 +1,7 @@
+//! Implementation details used by the code generated by the derive macros.
+//!
+//! Nothing in this module is part of the public API.
+pub mod de;
+pub mod ser;
+
+pub use serde;
@@ -0,0 +1,440 @@
+use std::fmt;
+
+use serde::{
+	ser::{Error, Impossible, SerializeMap, SerializeStruct},
+	Serialize, Serializer,
+};
+
+/// Serializer adding the tag of an internally tagged enum to the content of a
+/// newtype variant.
+///
+/// The content must be serialized as a struct, a map or a unit.
+pub struct TaggedSerializer<S> {
+	type_name: &'static str,
+	variant_ident: &'static str,
+	tag: &'static str,
+	variant_name: &'static str,
+	delegate: S,
+}
+
+impl<S> TaggedSerializer<S> {
+	pub fn new(
+		type_name: &'static str,
+		variant_ident: &'static str,
+		tag: &'static str,
+		variant_name: &'static str,
+		delegate: S,
+	) -> Self {
+		Self {
+			type_name,
+			variant_ident,
+			tag,
+			variant_name,
+			delegate,
+		}
+	}
+
+	fn bad_type<E: Error>(&self, what: impl fmt::Display) -> E {
+		E::custom(format_args!(
+			"cannot serialize tagged newtype variant {}::{} containing {}",
+			self.type_name, self.variant_ident, what
+		))
+	}
+}
+
+impl<S: Serializer> Serializer for TaggedSerializer<S> {
+	type Ok = S::Ok;
+	type Error = S::Error;
+
+	type SerializeSeq = Impossible<S::Ok, S::Error>;
+	type SerializeTuple = Impossible<S::Ok, S::Error>;
+	type SerializeTupleStruct = Impossible<S::Ok, S::Error>;
+	type SerializeTupleVariant = Impossible<S::Ok, S::Error>;
+	type SerializeMap = S::SerializeMap;
+	type SerializeStruct = S::SerializeStruct;
+	type SerializeStructVariant = Impossible<S::Ok, S::Error>;
+
+	fn serialize_bool(self, _v: bool) -> Result<S::Ok, S::Error> {
+		Err(self.bad_type("a boolean"))
+	}
+
+	fn serialize_i8(self, _v: i8) -> Result<S::Ok, S::Error> {
+		Err(self.bad_type("an integer"))
+	}
+
+	fn serialize_i16(self, _v: i16) -> Result<S::Ok, S::Error> {
+		Err(self.bad_type("an integer"))
+	}
+
+	fn serialize_i32(self, _v: i32) -> Result<S::Ok, S::Error> {
+		Err(self.bad_type("an integer"))
+	}
+
+	fn serialize_i64(self, _v: i64) -> Result<S::Ok, S::Error> {
+		Err(self.bad_type("an integer"))
+	}
+
+	fn serialize_u8(self, _v: u8) -> Result<S::Ok, S::Error> {
+		Err(self.bad_type("an integer"))
+	}
+
+	fn serialize_u16(self, _v: u16) -> Result<S::Ok, S::Error> {
+		Err(self.bad_type("an integer"))
+	}
+
+	fn serialize_u32(self, _v: u32) -> Result<S::Ok, S::Error> {
+		Err(self.bad_type("an integer"))
+	}
+
+	fn serialize_u64(self, _v: u64) -> Result<S::Ok, S::Error> {
+		Err(self.bad_type("an integer"))
+	}
+
+	fn serialize_f32(self, _v: f32) -> Result<S::Ok, S::Error> {
+		Err(self.bad_type("a float"))
+	}
+
+	fn serialize_f64(self, _v: f64) -> Result<S::Ok, S::Error> {
+		Err(self.bad_type("a float"))
+	}
+
+	fn serialize_char(self, _v: char) -> Result<S::Ok, S::Error> {
+		Err(self.bad_type("a char"))
+	}
+
+	fn serialize_str(self, _v: &str) -> Result<S::Ok, S::Error> {
+		Err(self.bad_type("a string"))
+	}
+
+	fn serialize_bytes(self, _v: &[u8]) -> Result<S::Ok, S::Error> {
+		Err(self.bad_type("a byte array"))
+	}
+
+	fn serialize_none(self) -> Result<S::Ok, S::Error> {
+		Err(self.bad_type("an optional"))
+	}
+
+	fn serialize_some<T>(self, _value: &T) -> Result<S::Ok, S::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		Err(self.bad_type("an optional"))
+	}
+
+	fn serialize_unit(self) -> Result<S::Ok, S::Error> {
+		let mut s = self.delegate.serialize_struct(self.type_name, 1)?;
+		s.serialize_field(self.tag, self.variant_name)?;
+		s.end()
+	}
+
+	fn serialize_unit_struct(self, _name: &'static str) -> Result<S::Ok, S::Error> {
+		self.serialize_unit()
+	}
+
+	fn serialize_unit_variant(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		_variant: &'static str,
+	) -> Result<S::Ok, S::Error> {
+		Err(self.bad_type("an enum"))
+	}
+
+	fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<S::Ok, S::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_newtype_variant<T>(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		_variant: &'static str,
+		_value: &T,
+	) -> Result<S::Ok, S::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		Err(self.bad_type("an enum"))
+	}
+
+	fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+		Err(self.bad_type("a sequence"))
+	}
+
+	fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, S::Error> {
+		Err(self.bad_type("a tuple"))
+	}
+
+	fn serialize_tuple_struct(
+		self,
+		_name: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeTupleStruct, S::Error> {
+		Err(self.bad_type("a tuple struct"))
+	}
+
+	fn serialize_tuple_variant(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		_variant: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeTupleVariant, S::Error> {
+		Err(self.bad_type("an enum"))
+	}
+
+	fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+		let mut s = self.delegate.serialize_map(len.map(|len| len + 1))?;
+		s.serialize_entry(self.tag, self.variant_name)?;
+		Ok(s)
+	}
+
+	fn serialize_struct(
+		self,
+		name: &'static str,
+		len: usize,
+	) -> Result<Self::SerializeStruct, S::Error> {
+		let mut s = self.delegate.serialize_struct(name, len + 1)?;
+		s.serialize_field(self.tag, self.variant_name)?;
+		Ok(s)
+	}
+
+	fn serialize_struct_variant(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		_variant: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeStructVariant, S::Error> {
+		Err(self.bad_type("an enum"))
+	}
+}
+
+/// Serializer inlining the entries of a flattened field into the map of the
+/// surrounding struct.
+pub struct FlatMapSerializer<'a, M>(pub &'a mut M);
+
+fn flat_map_unsupported<E: Error>(what: &str) -> E {
+	E::custom(format_args!(
+		"can only flatten structs and maps (got {what})"
+	))
+}
+
+impl<'a, M: SerializeMap> Serializer for FlatMapSerializer<'a, M> {
+	type Ok = ();
+	type Error = M::Error;
+
+	type SerializeSeq = Impossible<(), M::Error>;
+	type SerializeTuple = Impossible<(), M::Error>;
+	type SerializeTupleStruct = Impossible<(), M::Error>;
+	type SerializeTupleVariant = Impossible<(), M::Error>;
+	type SerializeMap = FlatMapSerializeMap<'a, M>;
+	type SerializeStruct = FlatMapSerializeMap<'a, M>;
+	type SerializeStructVariant = Impossible<(), M::Error>;
+
+	fn serialize_bool(self, _v: bool) -> Result<(), M::Error> {
+		Err(flat_map_unsupported("a boolean"))
+	}
+
+	fn serialize_i8(self, _v: i8) -> Result<(), M::Error> {
+		Err(flat_map_unsupported("an integer"))
+	}
+
+	fn serialize_i16(self, _v: i16) -> Result<(), M::Error> {
+		Err(flat_map_unsupported("an integer"))
+	}
+
+	fn serialize_i32(self, _v: i32) -> Result<(), M::Error> {
+		Err(flat_map_unsupported("an integer"))
+	}
+
+	fn serialize_i64(self, _v: i64) -> Result<(), M::Error> {
+		Err(flat_map_unsupported("an integer"))
+	}
+
+	fn serialize_u8(self, _v: u8) -> Result<(), M::Error> {
+		Err(flat_map_unsupported("an integer"))
+	}
+
+	fn serialize_u16(self, _v: u16) -> Result<(), M::Error> {
+		Err(flat_map_unsupported("an integer"))
+	}
+
+	fn serialize_u32(self, _v: u32) -> Result<(), M::Error> {
+		Err(flat_map_unsupported("an integer"))
+	}
+
+	fn serialize_u64(self, _v: u64) -> Result<(), M::Error> {
+		Err(flat_map_unsupported("an integer"))
+	}
+
+	fn serialize_f32(self, _v: f32) -> Result<(), M::Error> {
+		Err(flat_map_unsupported("a float"))
+	}
+
+	fn serialize_f64(self, _v: f64) -> Result<(), M::Error> {
+		Err(flat_map_unsupported("a float"))
+	}
+
+	fn serialize_char(self, _v: char) -> Result<(), M::Error> {
+		Err(flat_map_unsupported("a char"))
+	}
+
+	fn serialize_str(self, _v: &str) -> Result<(), M::Error> {
+		Err(flat_map_unsupported("a string"))
+	}
+
+	fn serialize_bytes(self, _v: &[u8]) -> Result<(), M::Error> {
+		Err(flat_map_unsupported("a byte array"))
+	}
+
+	fn serialize_none(self) -> Result<(), M::Error> {
+		Ok(())
+	}
+
+	fn serialize_some<T>(self, value: &T) -> Result<(), M::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_unit(self) -> Result<(), M::Error> {
+		Ok(())
+	}
+
+	fn serialize_unit_struct(self, _name: &'static str) -> Result<(), M::Error> {
+		Ok(())
+	}
+
+	fn serialize_unit_variant(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		_variant: &'static str,
+	) -> Result<(), M::Error> {
+		Err(flat_map_unsupported("an enum"))
+	}
+
+	fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<(), M::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_newtype_variant<T>(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		_variant: &'static str,
+		_value: &T,
+	) -> Result<(), M::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		Err(flat_map_unsupported("an enum"))
+	}
+
+	fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, M::Error> {
+		Err(flat_map_unsupported("a sequence"))
+	}
+
+	fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, M::Error> {
+		Err(flat_map_unsupported("a tuple"))
+	}
+
+	fn serialize_tuple_struct(
+		self,
+		_name: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeTupleStruct, M::Error> {
+		Err(flat_map_unsupported("a tuple struct"))
+	}
+
+	fn serialize_tuple_variant(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		_variant: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeTupleVariant, M::Error> {
+		Err(flat_map_unsupported("an enum"))
+	}
+
+	fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, M::Error> {
+		Ok(FlatMapSerializeMap(self.0))
+	}
+
+	fn serialize_struct(
+		self,
+		_name: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeStruct, M::Error> {
+		Ok(FlatMapSerializeMap(self.0))
+	}
+
+	fn serialize_struct_variant(
+		self,
+		_name: &'static str,
+		_variant_index: u32,
+		_variant: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeStructVariant, M::Error> {
+		Err(flat_map_unsupported("an enum"))
+	}
+}
+
+/// Map or struct serializer of a flattened field, writing the entries into
+/// the surrounding map.
+pub struct FlatMapSerializeMap<'a, M>(&'a mut M);
+
+impl<M: SerializeMap> SerializeMap for FlatMapSerializeMap<'_, M> {
+	type Ok = ();
+	type Error = M::Error;
+
+	fn serialize_key<T>(&mut self, key: &T) -> Result<(), M::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		self.0.serialize_key(key)
+	}
+
+	fn serialize_value<T>(&mut self, value: &T) -> Result<(), M::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		self.0.serialize_value(value)
+	}
+
+	fn serialize_entry<K, V>(&mut self, key: &K, value: &V) -> Result<(), M::Error>
+	where
+		K: ?Sized + Serialize,
+		V: ?Sized + Serialize,
+	{
+		self.0.serialize_entry(key, value)
+	}
+
+	fn end(self) -> Result<(), M::Error> {
+		Ok(())
+	}
+}
+
+impl<M: SerializeMap> SerializeStruct for FlatMapSerializeMap<'_, M> {
+	type Ok = ();
+	type Error = M::Error;
+
+	fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), M::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		self.0.serialize_entry(key, value)
+	}
+
+	fn end(self) -> Result<(), M::Error> {
+		Ok(())
+	}
+}
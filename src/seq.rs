@@ -0,0 +1,113 @@
+//! Seeded (de)serialization of sequences (`Vec<T>`, `VecDeque<T>`,
+//! `BTreeSet<T>`, `HashSet<T>`, etc.) where `T` requires a seed.
+//!
+//! ```ignore
+//! #[derive(SerializeSeeded, DeserializeSeeded)]
+//! #[seeded(serde(seed(Seed)))]
+//! struct Node {
+//!   #[seeded(with(serde_seeded::seq))]
+//!   children: Vec<Node>
+//! }
+//! ```
+use crate::{
+	de::{Borrowed, Exclusive, SeedFactory, Shared},
+	ser::{SeededMut, SeededRef},
+	DeserializeSeeded, DeserializeSeededMut, DeserializeSeededRef, SerializeSeeded,
+	SerializeSeededMut,
+};
+use serde::{ser::SerializeSeq, Deserializer, Serializer};
+use std::marker::PhantomData;
+
+pub fn serialize_seeded<C, T, Q, S>(value: &C, seed: &Q, serializer: S) -> Result<S::Ok, S::Error>
+where
+	Q: ?Sized,
+	C: ?Sized,
+	for<'a> &'a C: IntoIterator<Item = &'a T>,
+	T: SerializeSeeded<Q>,
+	S: Serializer,
+{
+	serializer.collect_seq(value.into_iter().map(|item| SeededRef::new(seed, item)))
+}
+
+pub fn serialize_seeded_mut<C, T, Q, S>(
+	value: &C,
+	seed: &mut Q,
+	serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	Q: ?Sized,
+	C: ?Sized,
+	for<'a> &'a C: IntoIterator<Item = &'a T>,
+	T: SerializeSeededMut<Q>,
+	S: Serializer,
+{
+	let items = value.into_iter();
+	let len = match items.size_hint() {
+		(min, Some(max)) if min == max => Some(min),
+		_ => None,
+	};
+
+	let mut s = serializer.serialize_seq(len)?;
+
+	for item in items {
+		s.serialize_element(&SeededMut::new(seed, item))?;
+	}
+
+	s.end()
+}
+
+pub fn deserialize_seeded<'de, C, T, Q, D>(seed: &Q, deserializer: D) -> Result<C, D::Error>
+where
+	C: FromIterator<T>,
+	Q: ?Sized,
+	T: DeserializeSeeded<'de, Q>,
+	D: Deserializer<'de>,
+{
+	deserializer.deserialize_seq(Visitor(Shared(seed), PhantomData))
+}
+
+pub fn deserialize_seeded_mut<'de, C, T, Q, D>(seed: &mut Q, deserializer: D) -> Result<C, D::Error>
+where
+	C: FromIterator<T>,
+	Q: ?Sized,
+	T: DeserializeSeededMut<'de, Q>,
+	D: Deserializer<'de>,
+{
+	deserializer.deserialize_seq(Visitor(Exclusive(seed), PhantomData))
+}
+
+pub fn deserialize_seeded_ref<'de, 'seed, C, T, Q, D>(
+	seed: &'seed Q,
+	deserializer: D,
+) -> Result<C, D::Error>
+where
+	C: FromIterator<T>,
+	Q: ?Sized,
+	T: DeserializeSeededRef<'de, 'seed, Q>,
+	D: Deserializer<'de>,
+{
+	deserializer.deserialize_seq(Visitor(Borrowed(seed), PhantomData))
+}
+
+/// Sequence visitor collecting items deserialized with the seeds of `F`
+/// directly into `C`.
+struct Visitor<F, T, C>(F, PhantomData<(T, C)>);
+
+impl<'de, F, T, C> ::serde::de::Visitor<'de> for Visitor<F, T, C>
+where
+	F: SeedFactory<'de, T>,
+	C: FromIterator<T>,
+{
+	type Value = C;
+
+	fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(formatter, "a sequence")
+	}
+
+	fn visit_seq<A>(mut self, mut seq: A) -> Result<Self::Value, A::Error>
+	where
+		A: serde::de::SeqAccess<'de>,
+	{
+		std::iter::from_fn(|| seq.next_element_seed(self.0.seed()).transpose()).collect()
+	}
+}
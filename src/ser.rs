@@ -1,27 +1,24 @@
-use std::collections::{BTreeMap, HashMap};
+use std::cell::RefCell;
 
-use serde::{
-	ser::{SerializeMap, SerializeSeq},
-	Serialize,
-};
+use serde::Serialize;
 
 /// Seeded value, ready to be serialized.
 ///
 /// This type implemented [`Serialize`] when `T` implements
-/// [`SerializeSeeded<Q>`].
-pub struct Seeded<'a, Q, T> {
+/// [`SerializeSeeded<Q>`]. To serialize a borrowed value, use [`SeededRef`].
+pub struct Seeded<'a, Q: ?Sized, T> {
 	pub seed: &'a Q,
 	pub value: T,
 }
 
-impl<'a, Q, T> Seeded<'a, Q, T> {
+impl<'a, Q: ?Sized, T> Seeded<'a, Q, T> {
 	/// Creates a new seeded value.
 	pub fn new(seed: &'a Q, value: T) -> Self {
 		Self { seed, value }
 	}
 }
 
-impl<Q, T: Clone> Clone for Seeded<'_, Q, T> {
+impl<Q: ?Sized, T: Clone> Clone for Seeded<'_, Q, T> {
 	fn clone(&self) -> Self {
 		Self {
 			seed: self.seed,
@@ -30,10 +27,11 @@ impl<Q, T: Clone> Clone for Seeded<'_, Q, T> {
 	}
 }
 
-impl<Q, T: Copy> Copy for Seeded<'_, Q, T> {}
+impl<Q: ?Sized, T: Copy> Copy for Seeded<'_, Q, T> {}
 
 impl<Q, T> Serialize for Seeded<'_, Q, T>
 where
+	Q: ?Sized,
 	T: SerializeSeeded<Q>,
 {
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -44,164 +42,128 @@ where
 	}
 }
 
-/// A data structure that can be serialized with a seed of type `Q`.
-pub trait SerializeSeeded<Q> {
-	/// Serializes the value using the given seed and serializer.
-	fn serialize_seeded<S>(&self, seed: &Q, serializer: S) -> Result<S::Ok, S::Error>
-	where
-		S: serde::Serializer;
-}
-
-impl<Q, T> SerializeSeeded<Q> for &T
-where
-	T: SerializeSeeded<Q>,
-{
-	fn serialize_seeded<S>(&self, seed: &Q, serializer: S) -> Result<S::Ok, S::Error>
-	where
-		S: serde::Serializer,
-	{
-		T::serialize_seeded(*self, seed, serializer)
-	}
-}
-
-impl<Q, T> SerializeSeeded<Q> for Box<T>
-where
-	T: SerializeSeeded<Q>,
-{
-	fn serialize_seeded<S>(&self, seed: &Q, serializer: S) -> Result<S::Ok, S::Error>
-	where
-		S: serde::Serializer,
-	{
-		T::serialize_seeded(self, seed, serializer)
-	}
+/// Seeded borrowed value, ready to be serialized.
+///
+/// This type implements [`Serialize`] when `T` implements
+/// [`SerializeSeeded<Q>`].
+pub struct SeededRef<'a, Q: ?Sized, T: ?Sized> {
+	pub seed: &'a Q,
+	pub value: &'a T,
 }
 
-impl<Q> SerializeSeeded<Q> for () {
-	fn serialize_seeded<S>(&self, _seed: &Q, serializer: S) -> Result<S::Ok, S::Error>
-	where
-		S: serde::Serializer,
-	{
-		serde::Serialize::serialize(self, serializer)
+impl<'a, Q: ?Sized, T: ?Sized> SeededRef<'a, Q, T> {
+	/// Creates a new seeded borrowed value.
+	pub fn new(seed: &'a Q, value: &'a T) -> Self {
+		Self { seed, value }
 	}
 }
 
-impl<Q> SerializeSeeded<Q> for bool {
-	fn serialize_seeded<S>(&self, _seed: &Q, serializer: S) -> Result<S::Ok, S::Error>
-	where
-		S: serde::Serializer,
-	{
-		serde::Serialize::serialize(self, serializer)
+impl<Q: ?Sized, T: ?Sized> Clone for SeededRef<'_, Q, T> {
+	fn clone(&self) -> Self {
+		*self
 	}
 }
 
-impl<Q> SerializeSeeded<Q> for u32 {
-	fn serialize_seeded<S>(&self, _seed: &Q, serializer: S) -> Result<S::Ok, S::Error>
-	where
-		S: serde::Serializer,
-	{
-		serde::Serialize::serialize(self, serializer)
-	}
-}
+impl<Q: ?Sized, T: ?Sized> Copy for SeededRef<'_, Q, T> {}
 
-impl<Q> SerializeSeeded<Q> for str {
-	fn serialize_seeded<S>(&self, _seed: &Q, serializer: S) -> Result<S::Ok, S::Error>
+impl<Q, T> Serialize for SeededRef<'_, Q, T>
+where
+	Q: ?Sized,
+	T: ?Sized + SerializeSeeded<Q>,
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer,
 	{
-		serde::Serialize::serialize(self, serializer)
+		self.value.serialize_seeded(self.seed, serializer)
 	}
 }
 
-impl<Q> SerializeSeeded<Q> for String {
-	fn serialize_seeded<S>(&self, _seed: &Q, serializer: S) -> Result<S::Ok, S::Error>
+/// A data structure that can be serialized with a seed of type `Q`.
+pub trait SerializeSeeded<Q: ?Sized> {
+	/// Serializes the value using the given seed and serializer.
+	fn serialize_seeded<S>(&self, seed: &Q, serializer: S) -> Result<S::Ok, S::Error>
 	where
-		S: serde::Serializer,
-	{
-		serde::Serialize::serialize(self, serializer)
-	}
+		S: serde::Serializer;
 }
 
-impl<Q, T> SerializeSeeded<Q> for Option<T>
+/// Any type that can be serialized without that seed (meaning they implement
+/// [`serde::Serialize`]), automatically implement [`SerializeSeeded`].
+///
+/// Containers of values requiring a seed (such as `Vec<T>` or `Option<T>`)
+/// can be serialized using the [`seq`](crate::seq),
+/// [`option`](crate::option), [`map`](crate::map), etc. modules with the
+/// `#[seeded(with(...))]` attribute.
+impl<Q, T> SerializeSeeded<Q> for T
 where
-	T: SerializeSeeded<Q>,
+	Q: ?Sized,
+	T: ?Sized + Serialize,
 {
-	fn serialize_seeded<S>(&self, seed: &Q, serializer: S) -> Result<S::Ok, S::Error>
+	fn serialize_seeded<S>(&self, _seed: &Q, serializer: S) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer,
 	{
-		match self {
-			Some(t) => serializer.serialize_some(&Seeded::new(seed, t)),
-			None => serializer.serialize_none(),
-		}
+		T::serialize(self, serializer)
 	}
 }
 
-impl<Q, T> SerializeSeeded<Q> for [T]
-where
-	T: SerializeSeeded<Q>,
-{
-	fn serialize_seeded<S>(&self, seed: &Q, serializer: S) -> Result<S::Ok, S::Error>
-	where
-		S: serde::Serializer,
-	{
-		let mut s = serializer.serialize_seq(Some(self.len()))?;
+/// Seeded value with a mutable seed, ready to be serialized.
+///
+/// This type implements [`Serialize`] when `T` implements
+/// [`SerializeSeededMut<Q>`]. Since [`Serialize::serialize`] only borrows
+/// `self`, the seed is stored in a [`RefCell`].
+pub struct SeededMut<'a, Q: ?Sized, T: ?Sized> {
+	seed: RefCell<&'a mut Q>,
+	value: &'a T,
+}
 
-		for item in self {
-			s.serialize_element(&Seeded::new(seed, item))?;
+impl<'a, Q: ?Sized, T: ?Sized> SeededMut<'a, Q, T> {
+	/// Creates a new seeded value.
+	pub fn new(seed: &'a mut Q, value: &'a T) -> Self {
+		Self {
+			seed: RefCell::new(seed),
+			value,
 		}
-
-		s.end()
 	}
 }
 
-impl<Q, T> SerializeSeeded<Q> for Vec<T>
+impl<Q, T> Serialize for SeededMut<'_, Q, T>
 where
-	T: SerializeSeeded<Q>,
+	Q: ?Sized,
+	T: ?Sized + SerializeSeededMut<Q>,
 {
-	fn serialize_seeded<S>(&self, seed: &Q, serializer: S) -> Result<S::Ok, S::Error>
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer,
 	{
-		let mut s = serializer.serialize_seq(Some(self.len()))?;
-
-		for item in self {
-			s.serialize_element(&Seeded::new(seed, item))?;
-		}
-
-		s.end()
+		let mut seed = self.seed.borrow_mut();
+		self.value.serialize_seeded_mut(*seed, serializer)
 	}
 }
 
-impl<Q, K, V> SerializeSeeded<Q> for BTreeMap<K, V>
-where
-	K: SerializeSeeded<Q>,
-	V: SerializeSeeded<Q>,
-{
-	fn serialize_seeded<S>(&self, seed: &Q, serializer: S) -> Result<S::Ok, S::Error>
+/// A data structure that can be serialized with a mutable seed of type `Q`.
+///
+/// The seed is passed to each serialized value in order, allowing it to
+/// record what has been emitted so far (string tables, back-references,
+/// etc.).
+pub trait SerializeSeededMut<Q: ?Sized> {
+	/// Serializes the value using the given mutable seed and serializer.
+	fn serialize_seeded_mut<S>(&self, seed: &mut Q, serializer: S) -> Result<S::Ok, S::Error>
 	where
-		S: serde::Serializer,
-	{
-		let mut map = serializer.serialize_map(Some(self.len()))?;
-		for (key, value) in self {
-			map.serialize_entry(&Seeded::new(seed, key), &Seeded::new(seed, value))?;
-		}
-		map.end()
-	}
+		S: serde::Serializer;
 }
 
-impl<Q, K, V> SerializeSeeded<Q> for HashMap<K, V>
+/// Any type that can be serialized without that seed (meaning they implement
+/// [`serde::Serialize`]), automatically implement [`SerializeSeededMut`].
+impl<Q, T> SerializeSeededMut<Q> for T
 where
-	K: SerializeSeeded<Q>,
-	V: SerializeSeeded<Q>,
+	Q: ?Sized,
+	T: ?Sized + Serialize,
 {
-	fn serialize_seeded<S>(&self, seed: &Q, serializer: S) -> Result<S::Ok, S::Error>
+	fn serialize_seeded_mut<S>(&self, _seed: &mut Q, serializer: S) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer,
 	{
-		let mut map = serializer.serialize_map(Some(self.len()))?;
-		for (key, value) in self {
-			map.serialize_entry(&Seeded::new(seed, key), &Seeded::new(seed, value))?;
-		}
-		map.end()
+		T::serialize(self, serializer)
 	}
 }
@@ -0,0 +1,158 @@
+use serde_seeded::{DeserializeSeeded, SerializeSeeded};
+
+mod common;
+use common::{from_json, offset, to_json, Offset};
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)), deny_unknown_fields)]
+pub struct Area {
+	width: u32,
+	height: u32,
+	/// Computed field, written out for consumers but rebuilt on load.
+	#[seeded(skip_deserializing)]
+	surface: u32,
+	/// Input-only field.
+	#[seeded(skip_serializing, default)]
+	comment: String,
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub struct Triple(
+	u32,
+	#[seeded(skip_deserializing, default_seeded = offset)] u32,
+	#[seeded(skip_serializing)] u32,
+	u32,
+);
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub enum Value {
+	Single(#[seeded(skip_deserializing)] u32, u32),
+	Nothing(#[seeded(skip_deserializing, default_seeded = offset)] u32),
+	Named {
+		#[seeded(skip_serializing, default_seeded = offset)]
+		input: u32,
+		#[seeded(skip_deserializing)]
+		output: u32,
+	},
+}
+
+#[test]
+fn named_fields() {
+	let seed = Offset(10);
+
+	let area = Area {
+		width: 2,
+		height: 3,
+		surface: 6,
+		comment: "hello".to_owned(),
+	};
+	let json = to_json(&seed, &area).unwrap();
+	assert_eq!(
+		json,
+		serde_json::json!({ "width": 2, "height": 3, "surface": 6 })
+	);
+
+	assert_eq!(
+		from_json::<Area>(
+			&seed,
+			serde_json::json!({ "width": 2, "height": 3, "comment": "hello" })
+		)
+		.unwrap(),
+		Area {
+			width: 2,
+			height: 3,
+			surface: 0,
+			comment: "hello".to_owned()
+		}
+	);
+
+	// Skipped fields are not expected when deserializing.
+	assert!(from_json::<Area>(
+		&seed,
+		serde_json::json!({ "width": 2, "height": 3, "surface": 6 })
+	)
+	.is_err());
+}
+
+#[test]
+fn unnamed_fields() {
+	let seed = Offset(10);
+
+	let json = to_json(&seed, &Triple(1, 2, 3, 4)).unwrap();
+	assert_eq!(json, serde_json::json!([1, 2, 4]));
+	assert_eq!(
+		from_json::<Triple>(&seed, serde_json::json!([1, 3, 4])).unwrap(),
+		Triple(1, 10, 3, 4)
+	);
+
+	assert_eq!(
+		from_json::<Value>(&seed, serde_json::json!({ "Single": 2 })).unwrap(),
+		Value::Single(0, 2)
+	);
+	assert_eq!(
+		from_json::<Value>(&seed, serde_json::json!("Nothing")).unwrap(),
+		Value::Nothing(10)
+	);
+}
+
+#[test]
+fn asymmetric_skip_edge_cases() {
+	let seed = Offset(10);
+
+	// Variants keep all their serialized fields, even when some of them are
+	// not deserialized.
+	let values = [
+		(Value::Single(1, 2), serde_json::json!({ "Single": [1, 2] })),
+		(Value::Nothing(1), serde_json::json!({ "Nothing": 1 })),
+		(
+			Value::Named {
+				input: 1,
+				output: 2,
+			},
+			serde_json::json!({ "Named": { "output": 2 } }),
+		),
+	];
+	for (value, expected) in values {
+		assert_eq!(to_json(&seed, &value).unwrap(), expected);
+	}
+
+	assert_eq!(
+		from_json::<Value>(&seed, serde_json::json!({ "Named": {} })).unwrap(),
+		Value::Named {
+			input: 10,
+			output: 0
+		}
+	);
+	assert_eq!(
+		from_json::<Value>(&seed, serde_json::json!({ "Named": { "input": 1 } })).unwrap(),
+		Value::Named {
+			input: 1,
+			output: 0
+		}
+	);
+
+	// Sequences only contain the deserialized fields.
+	assert_eq!(
+		from_json::<Area>(&seed, serde_json::json!([2, 3, "hello"])).unwrap(),
+		Area {
+			width: 2,
+			height: 3,
+			surface: 0,
+			comment: "hello".to_owned()
+		}
+	);
+	assert!(from_json::<Triple>(&seed, serde_json::json!([1, 3])).is_err());
+
+	// Fields only skipped when serializing are still optional with a default.
+	assert_eq!(
+		from_json::<Area>(&seed, serde_json::json!({ "width": 2, "height": 3 })).unwrap(),
+		Area {
+			width: 2,
+			height: 3,
+			surface: 0,
+			comment: String::new()
+		}
+	);
+}
@@ -0,0 +1,122 @@
+use serde_seeded::{ser::SeededMut, SerializeSeeded, SerializeSeededMut};
+
+/// Table of the strings emitted so far.
+#[derive(Default)]
+pub struct StringTable {
+	strings: Vec<String>,
+}
+
+/// Context wrapping the string table.
+#[derive(Default)]
+pub struct Context {
+	table: StringTable,
+	definitions: usize,
+}
+
+/// String emitted in full the first time, and as a back-reference to the
+/// first occurrence afterwards.
+#[derive(Debug, PartialEq)]
+pub struct Name(String);
+
+impl SerializeSeededMut<StringTable> for Name {
+	fn serialize_seeded_mut<S>(
+		&self,
+		seed: &mut StringTable,
+		serializer: S,
+	) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		match seed.strings.iter().position(|s| *s == self.0) {
+			Some(i) => serializer.serialize_u64(i as u64),
+			None => {
+				seed.strings.push(self.0.clone());
+				serializer.serialize_str(&self.0)
+			}
+		}
+	}
+}
+
+fn name(s: &str) -> Name {
+	Name(s.to_owned())
+}
+
+#[derive(SerializeSeeded)]
+#[seeded(ser_mut(seed(StringTable)))]
+pub struct Call {
+	function: Name,
+	#[seeded(with(serde_seeded::seq))]
+	args: Vec<Name>,
+	#[seeded(with(serde_seeded::option))]
+	target: Option<Name>,
+}
+
+#[derive(SerializeSeeded)]
+#[seeded(ser_mut(seed(StringTable)))]
+pub struct Pair(Name, Name);
+
+#[derive(SerializeSeeded)]
+#[seeded(ser_mut(seed(StringTable)), tag = "t", content = "c")]
+pub enum Operand {
+	Name(Name),
+	List(#[seeded(with(serde_seeded::seq))] Vec<Name>),
+}
+
+#[derive(SerializeSeeded)]
+#[seeded(ser_mut(seed(Context)))]
+pub struct Definition {
+	#[seeded(seed = &mut seed.table)]
+	name: Name,
+	#[seeded(skip_serializing_if_seeded = is_first_definition)]
+	index: usize,
+}
+
+fn is_first_definition(_: &usize, seed: &mut Context) -> bool {
+	seed.definitions += 1;
+	seed.definitions == 1
+}
+
+/// Serializes `value` to a JSON string.
+fn to_json<Q, T>(seed: &mut Q, value: &T) -> String
+where
+	T: SerializeSeededMut<Q>,
+{
+	serde_json::to_string(&SeededMut::new(seed, value)).unwrap()
+}
+
+#[test]
+fn back_references() {
+	let mut table = StringTable::default();
+
+	let call = Call {
+		function: name("f"),
+		args: vec![name("x"), name("y"), name("x")],
+		target: Some(name("f")),
+	};
+	assert_eq!(
+		to_json(&mut table, &call),
+		r#"{"function":"f","args":["x","y",1],"target":0}"#
+	);
+
+	let pair = Pair(name("z"), name("y"));
+	assert_eq!(to_json(&mut table, &pair), r#"["z",2]"#);
+
+	let operand = Operand::List(vec![name("w"), name("w")]);
+	assert_eq!(to_json(&mut table, &operand), r#"{"t":"List","c":["w",4]}"#);
+
+	let operand = Operand::Name(name("z"));
+	assert_eq!(to_json(&mut table, &operand), r#"{"t":"Name","c":3}"#);
+
+	assert_eq!(table.strings, ["f", "x", "y", "z", "w"]);
+}
+
+#[test]
+fn mutable_seed_projection() {
+	let mut context = Context::default();
+
+	let definition = Definition {
+		name: name("f"),
+		index: 0,
+	};
+	assert_eq!(to_json(&mut context, &definition), r#"{"name":"f"}"#);
+}
@@ -0,0 +1,120 @@
+use serde::de::{DeserializeSeed, Error};
+use serde_seeded::{de::SeedRef, DeserializeSeeded, DeserializeSeededRef};
+
+/// Type definitions, referenced by name in the serialized data.
+pub struct Schema {
+	types: Vec<TypeDef>,
+}
+
+/// Context wrapping the schema.
+pub struct Context {
+	schema: Schema,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TypeDef {
+	name: &'static str,
+}
+
+/// Type names are resolved into references to their definition in the
+/// schema.
+impl<'de, 'seed> DeserializeSeededRef<'de, 'seed, Schema> for &'seed TypeDef {
+	fn deserialize_seeded_ref<D>(seed: &'seed Schema, deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let name = String::deserialize_seeded(&(), deserializer)?;
+		seed.types
+			.iter()
+			.find(|ty| ty.name == name)
+			.ok_or_else(|| D::Error::custom(format!("unknown type `{name}`")))
+	}
+}
+
+#[derive(Debug, PartialEq, DeserializeSeeded)]
+#[seeded(de_ref(seed(Schema)))]
+pub struct Field<'seed> {
+	name: String,
+	ty: &'seed TypeDef,
+}
+
+#[derive(Debug, PartialEq, DeserializeSeeded)]
+#[seeded(de_ref(seed(Schema)))]
+pub struct Record<'seed> {
+	#[seeded(with(serde_seeded::seq))]
+	fields: Vec<Field<'seed>>,
+	#[seeded(with(serde_seeded::option))]
+	parent: Option<&'seed TypeDef>,
+}
+
+#[derive(Debug, PartialEq, DeserializeSeeded)]
+#[seeded(de_ref(seed(Schema)))]
+pub struct Pair<'seed>(&'seed TypeDef, &'seed TypeDef);
+
+#[derive(Debug, PartialEq, DeserializeSeeded)]
+#[seeded(de_ref(seed(Schema)), transparent)]
+pub struct TypeRef<'seed>(&'seed TypeDef);
+
+#[derive(Debug, PartialEq, DeserializeSeeded)]
+#[seeded(de_ref(seed(Schema)), tag = "kind")]
+pub enum Expr<'seed> {
+	Cast { ty: &'seed TypeDef, value: u32 },
+	Literal { value: u32 },
+}
+
+#[derive(Debug, PartialEq, DeserializeSeeded)]
+#[seeded(de_ref(seed(Context)))]
+pub struct Binding<'seed> {
+	name: String,
+	#[seeded(seed = &seed.schema)]
+	ty: &'seed TypeDef,
+}
+
+/// Deserializes `json` borrowing from `seed`.
+fn from_json<'de, 'seed, Q, T>(seed: &'seed Q, json: &'de str) -> Result<T, serde_json::Error>
+where
+	T: DeserializeSeededRef<'de, 'seed, Q>,
+{
+	SeedRef::new(seed).deserialize(&mut serde_json::Deserializer::from_str(json))
+}
+
+fn schema() -> Schema {
+	Schema {
+		types: vec![TypeDef { name: "int" }, TypeDef { name: "string" }],
+	}
+}
+
+#[test]
+fn borrowed_seed() {
+	let schema = schema();
+	let int = &schema.types[0];
+	let string = &schema.types[1];
+
+	let record: Record = from_json(
+		&schema,
+		r#"{ "fields": [{ "name": "id", "ty": "int" }, { "name": "label", "ty": "string" }], "parent": "int" }"#,
+	)
+	.unwrap();
+	assert!(std::ptr::eq(record.fields[0].ty, int));
+	assert!(std::ptr::eq(record.fields[1].ty, string));
+	assert!(std::ptr::eq(record.parent.unwrap(), int));
+
+	let pair: Pair = from_json(&schema, r#"["string", "int"]"#).unwrap();
+	assert_eq!(pair, Pair(string, int));
+
+	let ty: TypeRef = from_json(&schema, r#""string""#).unwrap();
+	assert!(std::ptr::eq(ty.0, string));
+
+	let expr: Expr = from_json(&schema, r#"{ "kind": "Cast", "ty": "int", "value": 1 }"#).unwrap();
+	assert_eq!(expr, Expr::Cast { ty: int, value: 1 });
+
+	assert!(from_json::<_, Field>(&schema, r#"{ "name": "x", "ty": "float" }"#).is_err());
+}
+
+#[test]
+fn borrowed_seed_projection() {
+	let context = Context { schema: schema() };
+
+	let binding: Binding = from_json(&context, r#"{ "name": "x", "ty": "string" }"#).unwrap();
+	assert!(std::ptr::eq(binding.ty, &context.schema.types[1]));
+}
@@ -0,0 +1,70 @@
+//! Fixtures shared by the integration tests.
+#![allow(dead_code)]
+use serde::de::DeserializeSeed;
+use serde_seeded::{de::Seed, ser::SeededRef, DeserializeSeeded, SerializeSeeded};
+
+/// Seed offsetting every number.
+pub struct Offset(pub u32);
+
+/// Number serialized with the offset of the seed added.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Number(pub u32);
+
+impl SerializeSeeded<Offset> for Number {
+	fn serialize_seeded<S>(&self, seed: &Offset, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serializer.serialize_u32(self.0 + seed.0)
+	}
+}
+
+impl<'de> DeserializeSeeded<'de, Offset> for Number {
+	fn deserialize_seeded<D>(seed: &Offset, deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let n = u32::deserialize_seeded(seed, deserializer)?;
+		n.checked_sub(seed.0)
+			.map(Self)
+			.ok_or_else(|| serde::de::Error::custom("number below the offset"))
+	}
+}
+
+/// Seeded default value, returning the offset itself.
+pub fn offset(seed: &Offset) -> u32 {
+	seed.0
+}
+
+/// Seeded `serialize_with` function adding the offset to a `u32`.
+pub fn serialize_offset<S: serde::Serializer>(
+	value: &u32,
+	seed: &Offset,
+	serializer: S,
+) -> Result<S::Ok, S::Error> {
+	serializer.serialize_u32(value + seed.0)
+}
+
+/// Seeded `deserialize_with` function removing the offset from a `u32`.
+pub fn deserialize_offset<'de, D: serde::Deserializer<'de>>(
+	seed: &Offset,
+	deserializer: D,
+) -> Result<u32, D::Error> {
+	let n: u32 = serde::Deserialize::deserialize(deserializer)?;
+	n.checked_sub(seed.0)
+		.ok_or_else(|| serde::de::Error::custom("number below the offset"))
+}
+
+pub fn to_json<T>(seed: &Offset, value: &T) -> Result<serde_json::Value, serde_json::Error>
+where
+	T: ?Sized + SerializeSeeded<Offset>,
+{
+	serde_json::to_value(SeededRef::new(seed, value))
+}
+
+pub fn from_json<T>(seed: &Offset, json: serde_json::Value) -> Result<T, serde_json::Error>
+where
+	T: for<'de> DeserializeSeeded<'de, Offset>,
+{
+	Seed::new(seed).deserialize(json)
+}
@@ -0,0 +1,152 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+
+use serde_seeded::{de::Seed, ser::SeededRef, DeserializeSeeded, SerializeSeeded};
+
+/// Seed prefixing every name.
+pub struct Prefix(&'static str);
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Name(String);
+
+impl SerializeSeeded<Prefix> for Name {
+	fn serialize_seeded<S>(&self, seed: &Prefix, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serializer.serialize_str(&format!("{}{}", seed.0, self.0))
+	}
+}
+
+impl<'de> DeserializeSeeded<'de, Prefix> for Name {
+	fn deserialize_seeded<D>(seed: &Prefix, deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let s = String::deserialize_seeded(seed, deserializer)?;
+		match s.strip_prefix(seed.0) {
+			Some(name) => Ok(Self(name.to_owned())),
+			None => Err(serde::de::Error::custom("missing prefix")),
+		}
+	}
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Prefix)))]
+pub struct Node {
+	id: u64,
+	weight: f64,
+	name: Name,
+	#[seeded(with(serde_seeded::option))]
+	parent: Option<Name>,
+	#[seeded(with(serde_seeded::seq))]
+	children: Vec<Node>,
+}
+
+#[test]
+fn serialize_containers() {
+	let node = Node {
+		id: 0,
+		weight: 0.5,
+		name: Name("root".to_owned()),
+		parent: None,
+		children: vec![Node {
+			id: 1,
+			weight: 1.5,
+			name: Name("child".to_owned()),
+			parent: Some(Name("root".to_owned())),
+			children: Vec::new(),
+		}],
+	};
+
+	let json = serde_json::to_value(SeededRef::new(&Prefix("x:"), &node)).unwrap();
+
+	assert_eq!(
+		json,
+		serde_json::json!({
+			"id": 0,
+			"weight": 0.5,
+			"name": "x:root",
+			"parent": null,
+			"children": [
+				{
+					"id": 1,
+					"weight": 1.5,
+					"name": "x:child",
+					"parent": "x:root",
+					"children": []
+				}
+			]
+		})
+	);
+
+	let back: Node =
+		serde::de::DeserializeSeed::deserialize(Seed::new(&Prefix("x:")), json).unwrap();
+	assert_eq!(back, node)
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Prefix)))]
+pub struct Collections {
+	#[seeded(with(serde_seeded::boxed))]
+	boxed: Box<Name>,
+	#[seeded(with(serde_seeded::array))]
+	array: [Name; 2],
+	#[seeded(with(serde_seeded::seq))]
+	deque: VecDeque<Name>,
+	#[seeded(with(serde_seeded::seq))]
+	btree_set: BTreeSet<Name>,
+	#[seeded(with(serde_seeded::seq))]
+	hash_set: HashSet<Name>,
+	#[seeded(with(serde_seeded::map))]
+	map: BTreeMap<Name, Name>,
+}
+
+#[test]
+fn round_trip_collections() {
+	let value = Collections {
+		boxed: Box::new(Name("a".to_owned())),
+		array: [Name("b".to_owned()), Name("c".to_owned())],
+		deque: [Name("d".to_owned())].into_iter().collect(),
+		btree_set: [Name("e".to_owned())].into_iter().collect(),
+		hash_set: [Name("f".to_owned())].into_iter().collect(),
+		map: [(Name("g".to_owned()), Name("h".to_owned()))]
+			.into_iter()
+			.collect(),
+	};
+
+	let seed = Prefix("x:");
+	let json = serde_json::to_string(&SeededRef::new(&seed, &value)).unwrap();
+	assert_eq!(
+		json,
+		r#"{"boxed":"x:a","array":["x:b","x:c"],"deque":["x:d"],"btree_set":["x:e"],"hash_set":["x:f"],"map":{"x:g":"x:h"}}"#
+	);
+
+	let mut deserializer = serde_json::Deserializer::from_str(&json);
+	let back = Collections::deserialize_seeded(&seed, &mut deserializer).unwrap();
+	assert_eq!(back, value)
+}
+
+#[test]
+fn top_level_containers() {
+	let seed = Prefix("x:");
+	let names = vec![Name("a".to_owned()), Name("b".to_owned())];
+
+	let items: Vec<_> = names
+		.iter()
+		.map(|name| SeededRef::new(&seed, name))
+		.collect();
+	let json = serde_json::to_string(&items).unwrap();
+	assert_eq!(json, r#"["x:a","x:b"]"#);
+
+	let mut deserializer = serde_json::Deserializer::from_str(&json);
+	let back: Vec<Name> = serde_seeded::seq::deserialize_seeded(&seed, &mut deserializer).unwrap();
+	assert_eq!(back, names);
+
+	let json = serde_json::to_string(&Some(SeededRef::new(&seed, &names[0]))).unwrap();
+	assert_eq!(json, r#""x:a""#);
+
+	let mut deserializer = serde_json::Deserializer::from_str("null");
+	let back: Option<Name> =
+		serde_seeded::option::deserialize_seeded(&seed, &mut deserializer).unwrap();
+	assert_eq!(back, None)
+}
@@ -0,0 +1,147 @@
+use serde_seeded::{DeserializeSeeded, SerializeSeeded};
+
+mod common;
+use common::{from_json, to_json, Number, Offset};
+
+/// Unchecked representation of a range.
+#[derive(SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub struct RawRange {
+	start: Number,
+	end: Number,
+}
+
+/// Range whose start is never after its end.
+#[derive(Debug, PartialEq, Clone, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)), try_from = "RawRange", into = "RawRange")]
+pub struct Range {
+	start: Number,
+	end: Number,
+}
+
+impl TryFrom<RawRange> for Range {
+	type Error = String;
+
+	fn try_from(value: RawRange) -> Result<Self, Self::Error> {
+		if value.start.0 <= value.end.0 {
+			Ok(Self {
+				start: value.start,
+				end: value.end,
+			})
+		} else {
+			Err("invalid range".to_owned())
+		}
+	}
+}
+
+impl From<Range> for RawRange {
+	fn from(value: Range) -> Self {
+		Self {
+			start: value.start,
+			end: value.end,
+		}
+	}
+}
+
+/// Range possibly reversed.
+#[derive(Debug, PartialEq, DeserializeSeeded)]
+#[seeded(de(seed(Offset)), from(RawRange))]
+pub struct SortedRange(Number, Number);
+
+impl From<RawRange> for SortedRange {
+	fn from(value: RawRange) -> Self {
+		if value.start.0 <= value.end.0 {
+			Self(value.start, value.end)
+		} else {
+			Self(value.end, value.start)
+		}
+	}
+}
+
+/// Label converted to and from a non-seeded type.
+#[derive(Debug, PartialEq, Clone, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)), from = "String", into = "String")]
+pub struct Label(String);
+
+impl From<String> for Label {
+	fn from(value: String) -> Self {
+		Self(value.to_uppercase())
+	}
+}
+
+impl From<Label> for String {
+	fn from(value: Label) -> Self {
+		value.0.to_lowercase()
+	}
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub struct Selection {
+	label: Label,
+	range: Range,
+}
+
+#[test]
+fn conversions() {
+	let seed = Offset(10);
+
+	let range = Range {
+		start: Number(1),
+		end: Number(2),
+	};
+	let json = to_json(&seed, &range).unwrap();
+	assert_eq!(json, serde_json::json!({ "start": 11, "end": 12 }));
+	assert_eq!(from_json::<Range>(&seed, json).unwrap(), range);
+
+	let reversed = serde_json::json!({ "start": 12, "end": 11 });
+	assert_eq!(
+		from_json::<Range>(&seed, reversed.clone())
+			.unwrap_err()
+			.to_string(),
+		"invalid range"
+	);
+	assert_eq!(
+		from_json::<SortedRange>(&seed, reversed).unwrap(),
+		SortedRange(Number(1), Number(2))
+	);
+}
+
+#[test]
+fn conversions_edge_cases() {
+	let seed = Offset(10);
+
+	// Conversions through types that are not seeded.
+	let selection = Selection {
+		label: Label("ALL".to_owned()),
+		range: Range {
+			start: Number(1),
+			end: Number(2),
+		},
+	};
+	let json = to_json(&seed, &selection).unwrap();
+	assert_eq!(
+		json,
+		serde_json::json!({ "label": "all", "range": { "start": 11, "end": 12 } })
+	);
+	assert_eq!(from_json::<Selection>(&seed, json).unwrap(), selection);
+
+	// Conversion errors of nested values are forwarded.
+	assert_eq!(
+		from_json::<Selection>(
+			&seed,
+			serde_json::json!({ "label": "all", "range": { "start": 12, "end": 11 } })
+		)
+		.unwrap_err()
+		.to_string(),
+		"invalid range"
+	);
+
+	// The source type is deserialized with the seed before the conversion.
+	assert_eq!(
+		from_json::<SortedRange>(&seed, serde_json::json!({ "start": 1, "end": 12 }))
+			.unwrap_err()
+			.to_string(),
+		"number below the offset"
+	);
+}
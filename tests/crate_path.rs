@@ -0,0 +1,130 @@
+use serde_seeded::{DeserializeSeeded, SerializeSeeded};
+
+mod common;
+use common::{from_json, offset, to_json, Number, Offset};
+
+/// Facade crate re-exporting `serde-seeded`.
+mod facade {
+	pub mod reexports {
+		pub use serde_seeded;
+	}
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)), crate = "facade::reexports::serde_seeded")]
+pub struct Point {
+	x: u32,
+	#[seeded(skip, default_seeded = offset)]
+	y: u32,
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(
+	serde(seed(Offset)),
+	crate(facade::reexports::serde_seeded),
+	tag = "type"
+)]
+pub enum Shape {
+	Point(Point),
+	Circle { radius: u32 },
+}
+
+/// Uses the runtime items needed by flattened and optional fields.
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)), crate = "facade::reexports::serde_seeded")]
+pub struct Marker {
+	#[seeded(flatten)]
+	point: Point,
+	#[seeded(with(facade::reexports::serde_seeded::option))]
+	weight: Option<Number>,
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(
+	serde(seed(Offset)),
+	crate = "facade::reexports::serde_seeded",
+	transparent
+)]
+pub struct Radius(Number);
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(
+	serde(seed(Offset)),
+	crate = "facade::reexports::serde_seeded",
+	tag = "t",
+	content = "c"
+)]
+pub enum Item {
+	Marker(Marker),
+	Radius(Radius),
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(
+	serde(seed(Offset)),
+	crate = "facade::reexports::serde_seeded",
+	untagged
+)]
+pub enum Size {
+	Radius(Radius),
+	Range(Number, Number),
+}
+
+#[test]
+fn crate_path() {
+	let seed = Offset(10);
+
+	let shape = Shape::Point(Point { x: 1, y: 10 });
+	let json = to_json(&seed, &shape).unwrap();
+	assert_eq!(json, serde_json::json!({ "type": "Point", "x": 1 }));
+	assert_eq!(from_json::<Shape>(&seed, json).unwrap(), shape);
+
+	let shape = Shape::Circle { radius: 2 };
+	let json = to_json(&seed, &shape).unwrap();
+	assert_eq!(json, serde_json::json!({ "type": "Circle", "radius": 2 }));
+	assert_eq!(from_json::<Shape>(&seed, json).unwrap(), shape);
+}
+
+#[test]
+fn crate_path_edge_cases() {
+	let seed = Offset(10);
+
+	let items = [
+		(
+			Item::Marker(Marker {
+				point: Point { x: 1, y: 10 },
+				weight: Some(Number(2)),
+			}),
+			serde_json::json!({ "t": "Marker", "c": { "x": 1, "weight": 12 } }),
+		),
+		(
+			Item::Marker(Marker {
+				point: Point { x: 1, y: 10 },
+				weight: None,
+			}),
+			serde_json::json!({ "t": "Marker", "c": { "x": 1, "weight": null } }),
+		),
+		(
+			Item::Radius(Radius(Number(3))),
+			serde_json::json!({ "t": "Radius", "c": 13 }),
+		),
+	];
+	for (item, expected) in items {
+		let json = to_json(&seed, &item).unwrap();
+		assert_eq!(json, expected);
+		assert_eq!(from_json::<Item>(&seed, json).unwrap(), item);
+	}
+
+	let sizes = [
+		(Size::Radius(Radius(Number(1))), serde_json::json!(11)),
+		(
+			Size::Range(Number(1), Number(2)),
+			serde_json::json!([11, 12]),
+		),
+	];
+	for (size, expected) in sizes {
+		let json = to_json(&seed, &size).unwrap();
+		assert_eq!(json, expected);
+		assert_eq!(from_json::<Size>(&seed, json).unwrap(), size);
+	}
+}
@@ -1,5 +1,5 @@
 #![no_std]
-#![allow(dead_code)]
+#![allow(dead_code, clippy::disallowed_names)]
 use serde_seeded::DeserializeSeeded;
 
 pub struct Seed;
@@ -1,12 +1,15 @@
 #![no_std]
-#![allow(dead_code)]
+#![allow(dead_code, clippy::disallowed_names)]
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
 use serde_seeded::SerializeSeeded;
 
 pub struct Seed;
 
 pub struct Seeded<T>(pub T);
 
-impl<'de, T> SerializeSeeded<Seed> for Seeded<T> {
+impl<T> SerializeSeeded<Seed> for Seeded<T> {
 	fn serialize_seeded<S>(&self, _seed: &Seed, _serializer: S) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer,
@@ -46,7 +49,7 @@ pub enum Bar {
 	Tuple(Seeded<u32>, Seeded<bool>),
 	Struct {
 		foo: Seeded<u32>,
-		#[seeded(skip_serializing_if(Option::is_none))]
+		#[seeded(with(serde_seeded::option), skip_serializing_if(Option::is_none))]
 		bar: Option<Seeded<bool>>,
 	},
 }
@@ -60,3 +63,27 @@ pub struct HybridStruct {
 	text: String,
 }
 static_assertions::assert_impl_all!(HybridStruct: SerializeSeeded<Seed>);
+
+#[derive(SerializeSeeded)]
+#[seeded(ser(seed(Seed)))]
+pub struct Primitives {
+	a: u64,
+	b: f64,
+	c: char,
+	d: (i8, u16),
+	e: Option<String>,
+	f: Vec<u32>,
+}
+static_assertions::assert_impl_all!(Primitives: SerializeSeeded<Seed>);
+
+#[derive(SerializeSeeded)]
+#[seeded(ser(seed(Seed)))]
+pub struct Containers {
+	#[seeded(with(serde_seeded::option))]
+	a: Option<Seeded<u32>>,
+	#[seeded(with(serde_seeded::seq))]
+	b: Vec<Seeded<u32>>,
+	#[seeded(with(serde_seeded::map))]
+	c: BTreeMap<Seeded<u32>, Seeded<bool>>,
+}
+static_assertions::assert_impl_all!(Containers: SerializeSeeded<Seed>);
@@ -0,0 +1,467 @@
+use serde::de::DeserializeSeed;
+use serde_seeded::{de::Seed, DeserializeSeeded, SerializeSeeded};
+
+mod common;
+use common::{from_json, to_json, Number, Offset};
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub enum Event {
+	#[seeded(rename = "start", alias = "begin")]
+	Start,
+	#[seeded(rename("move"))]
+	Move(Number),
+	#[seeded(skip)]
+	Internal(u32),
+	#[seeded(other)]
+	Unknown,
+}
+
+#[test]
+fn variant_attributes() {
+	let seed = Offset(10);
+
+	assert_eq!(
+		to_json(&seed, &Event::Start).unwrap(),
+		serde_json::json!("start")
+	);
+	assert_eq!(
+		to_json(&seed, &Event::Move(Number(1))).unwrap(),
+		serde_json::json!({ "move": 11 })
+	);
+	assert_eq!(
+		to_json(&seed, &Event::Internal(0)).unwrap_err().to_string(),
+		"the enum variant Event::Internal cannot be serialized"
+	);
+
+	assert_eq!(
+		from_json::<Event>(&seed, serde_json::json!("start")).unwrap(),
+		Event::Start
+	);
+	assert_eq!(
+		from_json::<Event>(&seed, serde_json::json!("begin")).unwrap(),
+		Event::Start
+	);
+	assert_eq!(
+		from_json::<Event>(&seed, serde_json::json!({ "move": 11 })).unwrap(),
+		Event::Move(Number(1))
+	);
+	assert_eq!(
+		from_json::<Event>(&seed, serde_json::json!("Internal")).unwrap(),
+		Event::Unknown
+	);
+	assert_eq!(
+		from_json::<Event>(&seed, serde_json::json!("stop")).unwrap(),
+		Event::Unknown
+	);
+
+	// Renamed variants are not known by their identifier anymore.
+	assert_eq!(
+		from_json::<Event>(&seed, serde_json::json!("Start")).unwrap(),
+		Event::Unknown
+	);
+	assert!(from_json::<Event>(&seed, serde_json::json!({ "Move": 11 })).is_err());
+
+	// `other` only accepts unit variants.
+	assert!(from_json::<Event>(&seed, serde_json::json!({ "stop": 11 })).is_err());
+}
+
+#[test]
+fn skipped_variants() {
+	let seed = Offset(10);
+
+	assert_eq!(
+		from_json::<Level>(&seed, serde_json::json!("High")).unwrap(),
+		Level::High
+	);
+	assert_eq!(
+		from_json::<Level>(&seed, serde_json::json!("Reserved"))
+			.unwrap_err()
+			.to_string(),
+		"unknown variant `Reserved`, expected `Low` or `High`"
+	);
+	assert_eq!(
+		to_json(&seed, &Level::Reserved).unwrap_err().to_string(),
+		"the enum variant Level::Reserved cannot be serialized"
+	);
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub enum Level {
+	Low,
+	#[seeded(skip)]
+	Reserved,
+	High,
+}
+
+/// Serializer returning the index of the serialized unit variant.
+struct VariantIndex;
+
+macro_rules! unsupported {
+	($($method:ident($ty:ty) -> $ok:ty),*) => {
+		$(
+			fn $method(self, _: $ty) -> Result<$ok, Self::Error> {
+				Err(serde::ser::Error::custom("expected a unit variant"))
+			}
+		)*
+	};
+}
+
+impl serde::Serializer for VariantIndex {
+	type Ok = u32;
+	type Error = serde::de::value::Error;
+	type SerializeSeq = serde::ser::Impossible<u32, Self::Error>;
+	type SerializeTuple = serde::ser::Impossible<u32, Self::Error>;
+	type SerializeTupleStruct = serde::ser::Impossible<u32, Self::Error>;
+	type SerializeTupleVariant = serde::ser::Impossible<u32, Self::Error>;
+	type SerializeMap = serde::ser::Impossible<u32, Self::Error>;
+	type SerializeStruct = serde::ser::Impossible<u32, Self::Error>;
+	type SerializeStructVariant = serde::ser::Impossible<u32, Self::Error>;
+
+	unsupported!(
+		serialize_bool(bool) -> u32,
+		serialize_i8(i8) -> u32,
+		serialize_i16(i16) -> u32,
+		serialize_i32(i32) -> u32,
+		serialize_i64(i64) -> u32,
+		serialize_u8(u8) -> u32,
+		serialize_u16(u16) -> u32,
+		serialize_u32(u32) -> u32,
+		serialize_u64(u64) -> u32,
+		serialize_f32(f32) -> u32,
+		serialize_f64(f64) -> u32,
+		serialize_char(char) -> u32,
+		serialize_str(&str) -> u32,
+		serialize_bytes(&[u8]) -> u32,
+		serialize_unit_struct(&'static str) -> u32,
+		serialize_seq(Option<usize>) -> Self::SerializeSeq,
+		serialize_tuple(usize) -> Self::SerializeTuple,
+		serialize_map(Option<usize>) -> Self::SerializeMap
+	);
+
+	fn serialize_unit_variant(
+		self,
+		_name: &'static str,
+		variant_index: u32,
+		_variant: &'static str,
+	) -> Result<u32, Self::Error> {
+		Ok(variant_index)
+	}
+
+	fn serialize_none(self) -> Result<u32, Self::Error> {
+		self.serialize_unit()
+	}
+
+	fn serialize_some<T: ?Sized + serde::Serialize>(self, _: &T) -> Result<u32, Self::Error> {
+		self.serialize_unit()
+	}
+
+	fn serialize_unit(self) -> Result<u32, Self::Error> {
+		Err(serde::ser::Error::custom("expected a unit variant"))
+	}
+
+	fn serialize_newtype_struct<T: ?Sized + serde::Serialize>(
+		self,
+		_: &'static str,
+		_: &T,
+	) -> Result<u32, Self::Error> {
+		self.serialize_unit()
+	}
+
+	fn serialize_newtype_variant<T: ?Sized + serde::Serialize>(
+		self,
+		_: &'static str,
+		_: u32,
+		_: &'static str,
+		_: &T,
+	) -> Result<u32, Self::Error> {
+		self.serialize_unit()
+	}
+
+	fn serialize_tuple_struct(
+		self,
+		_: &'static str,
+		_: usize,
+	) -> Result<Self::SerializeTupleStruct, Self::Error> {
+		Err(serde::ser::Error::custom("expected a unit variant"))
+	}
+
+	fn serialize_tuple_variant(
+		self,
+		_: &'static str,
+		_: u32,
+		_: &'static str,
+		_: usize,
+	) -> Result<Self::SerializeTupleVariant, Self::Error> {
+		Err(serde::ser::Error::custom("expected a unit variant"))
+	}
+
+	fn serialize_struct(
+		self,
+		_: &'static str,
+		_: usize,
+	) -> Result<Self::SerializeStruct, Self::Error> {
+		Err(serde::ser::Error::custom("expected a unit variant"))
+	}
+
+	fn serialize_struct_variant(
+		self,
+		_: &'static str,
+		_: u32,
+		_: &'static str,
+		_: usize,
+	) -> Result<Self::SerializeStructVariant, Self::Error> {
+		Err(serde::ser::Error::custom("expected a unit variant"))
+	}
+}
+
+/// Variant indices are declaration indices, skipped variants included.
+#[test]
+fn variant_index() {
+	use serde::de::IntoDeserializer;
+
+	let seed = Offset(10);
+
+	assert_eq!(Level::Low.serialize_seeded(&seed, VariantIndex), Ok(0));
+	assert_eq!(Level::High.serialize_seeded(&seed, VariantIndex), Ok(2));
+	assert!(Level::Reserved
+		.serialize_seeded(&seed, VariantIndex)
+		.is_err());
+
+	let from_index = |i: u32| {
+		Seed::<_, Level>::new(&seed)
+			.deserialize(IntoDeserializer::<serde::de::value::Error>::into_deserializer(i))
+	};
+	assert_eq!(from_index(0), Ok(Level::Low));
+	assert_eq!(from_index(2), Ok(Level::High));
+	assert!(from_index(1).is_err());
+	assert!(from_index(3).is_err());
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(
+	serde(seed(Offset)),
+	rename_all = "snake_case",
+	rename_all_fields = "camelCase"
+)]
+pub enum Shape {
+	UnitSquare,
+	MovedPoint {
+		new_x: Number,
+		new_y: Number,
+	},
+	#[seeded(rename_all = "SCREAMING-KEBAB-CASE")]
+	ScaledPoint {
+		scale_factor: u32,
+	},
+}
+
+#[test]
+fn rename_all() {
+	let seed = Offset(10);
+
+	let values = [
+		(Shape::UnitSquare, serde_json::json!("unit_square")),
+		(
+			Shape::MovedPoint {
+				new_x: Number(1),
+				new_y: Number(2),
+			},
+			serde_json::json!({ "moved_point": { "newX": 11, "newY": 12 } }),
+		),
+		(
+			Shape::ScaledPoint { scale_factor: 3 },
+			serde_json::json!({ "scaled_point": { "SCALE-FACTOR": 3 } }),
+		),
+	];
+
+	for (value, expected) in values {
+		let json = to_json(&seed, &value).unwrap();
+		assert_eq!(json, expected);
+		assert_eq!(from_json::<Shape>(&seed, json).unwrap(), value)
+	}
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub struct Point {
+	x: Number,
+	y: Number,
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)), tag = "type")]
+pub enum Internal {
+	Empty,
+	Point(Point),
+	Line { from: Number, to: Number },
+}
+
+#[test]
+fn internally_tagged() {
+	let seed = Offset(10);
+
+	let values = [
+		(Internal::Empty, serde_json::json!({ "type": "Empty" })),
+		(
+			Internal::Point(Point {
+				x: Number(1),
+				y: Number(2),
+			}),
+			serde_json::json!({ "type": "Point", "x": 11, "y": 12 }),
+		),
+		(
+			Internal::Line {
+				from: Number(1),
+				to: Number(2),
+			},
+			serde_json::json!({ "type": "Line", "from": 11, "to": 12 }),
+		),
+	];
+
+	for (value, expected) in values {
+		let json = to_json(&seed, &value).unwrap();
+		assert_eq!(json, expected);
+		assert_eq!(from_json::<Internal>(&seed, json).unwrap(), value)
+	}
+
+	let value: Internal = Seed::new(&seed)
+		.deserialize(&mut serde_json::Deserializer::from_str(
+			r#"{ "to": 12, "type": "Line", "from": 11 }"#,
+		))
+		.unwrap();
+	assert_eq!(
+		value,
+		Internal::Line {
+			from: Number(1),
+			to: Number(2)
+		}
+	);
+
+	assert!(from_json::<Internal>(&seed, serde_json::json!({ "x": 11 })).is_err());
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)), tag = "t", content = "c")]
+pub enum Adjacent {
+	Empty,
+	Number(Number),
+	Pair(Number, Number),
+	Line { from: Number, to: Number },
+}
+
+#[test]
+fn adjacently_tagged() {
+	let seed = Offset(10);
+
+	let values = [
+		(Adjacent::Empty, serde_json::json!({ "t": "Empty" })),
+		(
+			Adjacent::Number(Number(1)),
+			serde_json::json!({ "t": "Number", "c": 11 }),
+		),
+		(
+			Adjacent::Pair(Number(1), Number(2)),
+			serde_json::json!({ "t": "Pair", "c": [11, 12] }),
+		),
+		(
+			Adjacent::Line {
+				from: Number(1),
+				to: Number(2),
+			},
+			serde_json::json!({ "t": "Line", "c": { "from": 11, "to": 12 } }),
+		),
+	];
+
+	for (value, expected) in values {
+		let json = to_json(&seed, &value).unwrap();
+		assert_eq!(json, expected);
+		assert_eq!(from_json::<Adjacent>(&seed, json).unwrap(), value)
+	}
+
+	// The tag may come before or after the content, or use the sequence form.
+	for json in [
+		r#"{ "t": "Line", "c": { "from": 11, "to": 12 } }"#,
+		r#"{ "c": { "from": 11, "to": 12 }, "t": "Line" }"#,
+		r#"["Line", { "from": 11, "to": 12 }]"#,
+	] {
+		let value: Adjacent = Seed::new(&seed)
+			.deserialize(&mut serde_json::Deserializer::from_str(json))
+			.unwrap();
+		assert_eq!(
+			value,
+			Adjacent::Line {
+				from: Number(1),
+				to: Number(2)
+			}
+		);
+	}
+
+	assert!(from_json::<Adjacent>(&seed, serde_json::json!({ "c": 11 })).is_err());
+}
+
+#[derive(Debug, PartialEq, DeserializeSeeded)]
+#[seeded(de(seed(Offset)), tag = "t", content = "c", deny_unknown_fields)]
+pub enum StrictAdjacent {
+	Number(Number),
+}
+
+#[test]
+fn adjacently_tagged_deny_unknown_fields() {
+	let seed = Offset(10);
+
+	assert_eq!(
+		from_json::<StrictAdjacent>(&seed, serde_json::json!({ "t": "Number", "c": 11 })).unwrap(),
+		StrictAdjacent::Number(Number(1))
+	);
+
+	let error =
+		from_json::<StrictAdjacent>(&seed, serde_json::json!({ "t": "Number", "c": 11, "x": 0 }))
+			.unwrap_err()
+			.to_string();
+	assert_eq!(error, "unknown field `x`, expected `t` or `c`")
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)), untagged)]
+pub enum Legacy {
+	Nothing,
+	Id(Number),
+	Name(String),
+	Pair(Number, Number),
+	Full { id: Number, name: String },
+}
+
+#[test]
+fn untagged() {
+	let seed = Offset(10);
+
+	let values = [
+		(Legacy::Nothing, serde_json::json!(null)),
+		(Legacy::Id(Number(1)), serde_json::json!(11)),
+		(Legacy::Name("foo".to_owned()), serde_json::json!("foo")),
+		(
+			Legacy::Pair(Number(1), Number(2)),
+			serde_json::json!([11, 12]),
+		),
+		(
+			Legacy::Full {
+				id: Number(1),
+				name: "foo".to_owned(),
+			},
+			serde_json::json!({ "id": 11, "name": "foo" }),
+		),
+	];
+
+	for (value, expected) in values {
+		let json = to_json(&seed, &value).unwrap();
+		assert_eq!(json, expected);
+		assert_eq!(from_json::<Legacy>(&seed, json).unwrap(), value)
+	}
+
+	let error = from_json::<Legacy>(&seed, serde_json::json!(true)).unwrap_err();
+	assert!(error
+		.to_string()
+		.starts_with("data did not match any variant of untagged enum Legacy: Nothing: "));
+}
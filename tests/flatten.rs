@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use serde::de::DeserializeSeed;
+use serde_seeded::{de::Seed, DeserializeSeeded, SerializeSeeded};
+
+mod common;
+use common::{from_json, to_json, Number, Offset};
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub struct Header {
+	id: Number,
+	version: u32,
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub struct Record {
+	#[seeded(flatten)]
+	header: Header,
+	value: Number,
+	#[seeded(flatten, with(serde_seeded::map))]
+	extra: HashMap<String, Number>,
+}
+
+#[test]
+fn flatten() {
+	let seed = Offset(10);
+
+	let value = Record {
+		header: Header {
+			id: Number(1),
+			version: 2,
+		},
+		value: Number(3),
+		extra: [("foo".to_owned(), Number(4))].into_iter().collect(),
+	};
+
+	let json = to_json(&seed, &value).unwrap();
+	assert_eq!(
+		json,
+		serde_json::json!({ "id": 11, "version": 2, "value": 13, "foo": 14 })
+	);
+	assert_eq!(from_json::<Record>(&seed, json).unwrap(), value);
+
+	assert_eq!(
+		from_json::<Record>(&seed, serde_json::json!({ "id": 11, "value": 13 }))
+			.unwrap_err()
+			.to_string(),
+		"missing field `version`"
+	);
+}
+
+#[test]
+fn flatten_edge_cases() {
+	let seed = Offset(10);
+
+	// Flattened keys may be interleaved with the other keys.
+	let value: Record = Seed::new(&seed)
+		.deserialize(&mut serde_json::Deserializer::from_str(
+			r#"{ "version": 2, "value": 13, "id": 11 }"#,
+		))
+		.unwrap();
+	assert_eq!(
+		value,
+		Record {
+			header: Header {
+				id: Number(1),
+				version: 2
+			},
+			value: Number(3),
+			extra: HashMap::new()
+		}
+	);
+
+	// An empty catch-all map adds no key.
+	let json = to_json(&seed, &value).unwrap();
+	assert_eq!(
+		json,
+		serde_json::json!({ "id": 11, "version": 2, "value": 13 })
+	);
+
+	// Leftover values are deserialized with the seed.
+	assert_eq!(
+		from_json::<Record>(
+			&seed,
+			serde_json::json!({ "id": 11, "version": 2, "value": 13, "foo": 4 })
+		)
+		.unwrap_err()
+		.to_string(),
+		"number below the offset"
+	);
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub struct Revision {
+	#[seeded(flatten)]
+	header: Header,
+	revision: Number,
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub struct Document {
+	name: String,
+	#[seeded(flatten)]
+	revision: Revision,
+}
+
+#[test]
+fn nested_flatten() {
+	let seed = Offset(10);
+
+	let value = Document {
+		name: "doc".to_owned(),
+		revision: Revision {
+			header: Header {
+				id: Number(1),
+				version: 2,
+			},
+			revision: Number(3),
+		},
+	};
+
+	let json = to_json(&seed, &value).unwrap();
+	assert_eq!(
+		json,
+		serde_json::json!({ "name": "doc", "id": 11, "version": 2, "revision": 13 })
+	);
+	assert_eq!(from_json::<Document>(&seed, json).unwrap(), value);
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)), tag = "type")]
+pub enum Message {
+	Ping {
+		#[seeded(flatten)]
+		header: Header,
+	},
+	Data {
+		#[seeded(flatten)]
+		header: Header,
+		value: Number,
+	},
+}
+
+#[test]
+fn flatten_variant() {
+	let seed = Offset(10);
+
+	let value = Message::Data {
+		header: Header {
+			id: Number(1),
+			version: 2,
+		},
+		value: Number(3),
+	};
+
+	let json = to_json(&seed, &value).unwrap();
+	assert_eq!(
+		json,
+		serde_json::json!({ "type": "Data", "id": 11, "version": 2, "value": 13 })
+	);
+	assert_eq!(from_json::<Message>(&seed, json).unwrap(), value);
+}
@@ -0,0 +1,124 @@
+use serde::de::DeserializeSeed;
+use serde_seeded::{de::Seed, ser::SeededRef, DeserializeSeeded, SerializeSeeded};
+
+mod common;
+use common::{from_json, Number, Offset};
+
+/// Seed scaling every number.
+pub struct Scale(u32);
+
+impl SerializeSeeded<Scale> for Number {
+	fn serialize_seeded<S>(&self, seed: &Scale, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serializer.serialize_u32(self.0 * seed.0)
+	}
+}
+
+impl<'de> DeserializeSeeded<'de, Scale> for Number {
+	fn deserialize_seeded<D>(seed: &Scale, deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let n = u32::deserialize_seeded(seed, deserializer)?;
+		Ok(Self(n / seed.0))
+	}
+}
+
+/// Building block usable with any seed supported by its fields.
+///
+/// Fields using `with` are not bounded automatically.
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(
+	ser(bounds(Number: SerializeSeeded<__Q>)),
+	de(bounds(Number: DeserializeSeeded<'de, __Q>))
+)]
+pub struct Labeled<T> {
+	label: String,
+	value: T,
+	#[seeded(with(serde_seeded::option))]
+	extra: Option<Number>,
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+pub enum Measure {
+	Single(Number),
+	Range { from: Number, to: Number },
+}
+
+/// Does not depend on the seed at all.
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+pub enum Unit {
+	Meter,
+	Second,
+}
+
+fn round_trip<Q, T>(seed: &Q, value: &T, expected: serde_json::Value)
+where
+	T: std::fmt::Debug + PartialEq + SerializeSeeded<Q> + for<'de> DeserializeSeeded<'de, Q>,
+{
+	let json = serde_json::to_value(SeededRef::new(seed, value)).unwrap();
+	assert_eq!(json, expected);
+	assert_eq!(&Seed::<Q, T>::new(seed).deserialize(json).unwrap(), value)
+}
+
+#[test]
+fn generic_seed() {
+	let value = Labeled {
+		label: "length".to_owned(),
+		value: Measure::Range {
+			from: Number(1),
+			to: Number(2),
+		},
+		extra: Some(Number(3)),
+	};
+
+	round_trip(
+		&Offset(10),
+		&value,
+		serde_json::json!({
+			"label": "length",
+			"value": { "Range": { "from": 11, "to": 12 } },
+			"extra": 13
+		}),
+	);
+
+	round_trip(
+		&Scale(10),
+		&value,
+		serde_json::json!({
+			"label": "length",
+			"value": { "Range": { "from": 10, "to": 20 } },
+			"extra": 30
+		}),
+	);
+
+	round_trip(
+		&Scale(10),
+		&Measure::Single(Number(4)),
+		serde_json::json!({ "Single": 40 }),
+	);
+}
+
+#[test]
+fn generic_seed_edge_cases() {
+	// Types without seeded fields accept any seed.
+	round_trip(&(), &Unit::Meter, serde_json::json!("Meter"));
+	round_trip(&Offset(10), &Unit::Second, serde_json::json!("Second"));
+
+	// Generic type parameters are bounded with the same seed.
+	round_trip(
+		&Offset(10),
+		&Labeled {
+			label: "count".to_owned(),
+			value: Number(1),
+			extra: None,
+		},
+		serde_json::json!({ "label": "count", "value": 11, "extra": null }),
+	);
+
+	// Errors of the selected seed are forwarded.
+	let error = from_json::<Measure>(&Offset(10), serde_json::json!({ "Single": 4 })).unwrap_err();
+	assert_eq!(error.to_string(), "number below the offset");
+}
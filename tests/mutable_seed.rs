@@ -0,0 +1,149 @@
+use serde::de::DeserializeSeed;
+use serde_seeded::{de::SeedMut, DeserializeSeeded, DeserializeSeededMut};
+
+/// Interner assigning a fresh id to each new name.
+#[derive(Default)]
+pub struct Interner {
+	names: Vec<String>,
+}
+
+impl Interner {
+	fn intern(&mut self, name: String) -> usize {
+		match self.names.iter().position(|n| *n == name) {
+			Some(i) => i,
+			None => {
+				self.names.push(name);
+				self.names.len() - 1
+			}
+		}
+	}
+}
+
+/// Context wrapping the interner.
+#[derive(Default)]
+pub struct Context {
+	interner: Interner,
+	definitions: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Symbol(usize);
+
+impl<'de> DeserializeSeededMut<'de, Interner> for Symbol {
+	fn deserialize_seeded_mut<D>(seed: &mut Interner, deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let name = String::deserialize_seeded(&(), deserializer)?;
+		Ok(Self(seed.intern(name)))
+	}
+}
+
+#[derive(Debug, PartialEq, DeserializeSeeded)]
+#[seeded(de_mut(seed(Interner)))]
+pub struct Call {
+	function: Symbol,
+	#[seeded(with(serde_seeded::seq))]
+	args: Vec<Symbol>,
+	#[seeded(with(serde_seeded::option))]
+	target: Option<Symbol>,
+}
+
+#[derive(Debug, PartialEq, DeserializeSeeded)]
+#[seeded(de_mut(seed(Interner)))]
+pub struct Pair(Symbol, Symbol);
+
+#[derive(Debug, PartialEq, DeserializeSeeded)]
+#[seeded(de_mut(seed(Interner)), tag = "type")]
+pub enum Statement {
+	Call(Call),
+	Assign { target: Symbol, value: Symbol },
+}
+
+#[derive(Debug, PartialEq, DeserializeSeeded)]
+#[seeded(de_mut(seed(Interner)), tag = "t", content = "c")]
+pub enum Operand {
+	Symbol(Symbol),
+	List(#[seeded(with(serde_seeded::seq))] Vec<Symbol>),
+}
+
+#[derive(Debug, PartialEq, DeserializeSeeded)]
+#[seeded(de_mut(seed(Context)), validate = count_definition)]
+pub struct Definition {
+	#[seeded(seed = &mut seed.interner)]
+	name: Symbol,
+	#[seeded(seed = &mut seed.interner, with(serde_seeded::seq))]
+	body: Vec<Symbol>,
+}
+
+/// Deserializes `json` in document order.
+fn from_json<'de, Q, T>(seed: &mut Q, json: &'de str) -> Result<T, serde_json::Error>
+where
+	T: DeserializeSeededMut<'de, Q>,
+{
+	SeedMut::new(seed).deserialize(&mut serde_json::Deserializer::from_str(json))
+}
+
+fn count_definition(_: &Definition, seed: &mut Context) -> Result<(), std::convert::Infallible> {
+	seed.definitions += 1;
+	Ok(())
+}
+
+#[test]
+fn mutable_seed() {
+	let mut interner = Interner::default();
+
+	let call: Call = from_json(
+		&mut interner,
+		r#"{ "function": "f", "args": ["x", "y", "x"], "target": "y" }"#,
+	)
+	.unwrap();
+	assert_eq!(
+		call,
+		Call {
+			function: Symbol(0),
+			args: vec![Symbol(1), Symbol(2), Symbol(1)],
+			target: Some(Symbol(2))
+		}
+	);
+
+	let pair: Pair = from_json(&mut interner, r#"["z", "f"]"#).unwrap();
+	assert_eq!(pair, Pair(Symbol(3), Symbol(0)));
+
+	let statement: Statement = from_json(
+		&mut interner,
+		r#"{ "type": "Assign", "target": "w", "value": "x" }"#,
+	)
+	.unwrap();
+	assert_eq!(
+		statement,
+		Statement::Assign {
+			target: Symbol(4),
+			value: Symbol(1)
+		}
+	);
+
+	let operand: Operand = from_json(&mut interner, r#"{ "c": ["v", "w"], "t": "List" }"#).unwrap();
+	assert_eq!(operand, Operand::List(vec![Symbol(5), Symbol(4)]));
+
+	assert_eq!(interner.names, ["f", "x", "y", "z", "w", "v"]);
+}
+
+#[test]
+fn mutable_seed_projection() {
+	let mut context = Context::default();
+
+	for _ in 0..2 {
+		let definition: Definition =
+			from_json(&mut context, r#"{ "name": "f", "body": ["x", "f"] }"#).unwrap();
+		assert_eq!(
+			definition,
+			Definition {
+				name: Symbol(0),
+				body: vec![Symbol(1), Symbol(0)]
+			}
+		);
+	}
+
+	assert_eq!(context.definitions, 2);
+}
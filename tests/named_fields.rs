@@ -0,0 +1,255 @@
+use serde_seeded::{DeserializeSeeded, SerializeSeeded};
+
+mod common;
+use common::{from_json, to_json, Number, Offset};
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub struct Point {
+	x: Number,
+	#[seeded(skip)]
+	cached: u32,
+	y: Number,
+	#[seeded(default)]
+	z: u32,
+}
+
+#[derive(Debug, PartialEq, DeserializeSeeded)]
+#[seeded(de(seed(Offset)))]
+pub enum Shape {
+	Point { x: Number, y: Number },
+}
+
+#[test]
+fn struct_from_seq() {
+	let seed = Offset(10);
+
+	assert_eq!(
+		from_json::<Point>(&seed, serde_json::json!([11, 12, 3])).unwrap(),
+		Point {
+			x: Number(1),
+			cached: 0,
+			y: Number(2),
+			z: 3
+		}
+	);
+
+	assert_eq!(
+		from_json::<Point>(&seed, serde_json::json!([11, 12])).unwrap(),
+		Point {
+			x: Number(1),
+			cached: 0,
+			y: Number(2),
+			z: 0
+		}
+	);
+
+	assert_eq!(
+		from_json::<Point>(&seed, serde_json::json!([11]))
+			.unwrap_err()
+			.to_string(),
+		"invalid length 1, expected struct Point with 3 elements"
+	);
+	assert_eq!(
+		from_json::<Point>(&seed, serde_json::json!([11, 12, 3, 4]))
+			.unwrap_err()
+			.to_string(),
+		"invalid length 4, expected fewer elements in array"
+	);
+	assert_eq!(
+		from_json::<Point>(&seed, serde_json::json!([11, 2]))
+			.unwrap_err()
+			.to_string(),
+		"number below the offset"
+	);
+
+	// The sequence form ignores `deny_unknown_fields`, since it has no keys.
+	assert_eq!(
+		from_json::<StrictPoint>(&seed, serde_json::json!([11, 12])).unwrap(),
+		StrictPoint {
+			x: Number(1),
+			y: Number(2)
+		}
+	);
+
+	let mut deserializer = serde_json::Deserializer::from_str(r#"{ "Point": [11, 12] }"#);
+	assert_eq!(
+		Shape::deserialize_seeded(&seed, &mut deserializer).unwrap(),
+		Shape::Point {
+			x: Number(1),
+			y: Number(2)
+		}
+	)
+}
+
+#[test]
+fn struct_round_trip() {
+	let seed = Offset(10);
+	let value = Point {
+		x: Number(1),
+		cached: 0,
+		y: Number(2),
+		z: 3,
+	};
+
+	let json = to_json(&seed, &value).unwrap();
+	assert_eq!(json, serde_json::json!({ "x": 11, "y": 12, "z": 3 }));
+	assert_eq!(from_json::<Point>(&seed, json).unwrap(), value)
+}
+
+#[test]
+fn ignore_unknown_fields() {
+	let seed = Offset(10);
+
+	assert_eq!(
+		from_json::<Point>(
+			&seed,
+			serde_json::json!({ "x": 11, "w": [1, 2], "y": 12, "z": 3 })
+		)
+		.unwrap(),
+		Point {
+			x: Number(1),
+			cached: 0,
+			y: Number(2),
+			z: 3
+		}
+	)
+}
+
+#[derive(Debug, PartialEq, DeserializeSeeded)]
+#[seeded(de(seed(Offset)), deny_unknown_fields)]
+pub struct StrictPoint {
+	x: Number,
+	y: Number,
+}
+
+#[test]
+fn deny_unknown_fields() {
+	let seed = Offset(10);
+
+	assert_eq!(
+		from_json::<StrictPoint>(&seed, serde_json::json!({ "x": 11, "y": 12 })).unwrap(),
+		StrictPoint {
+			x: Number(1),
+			y: Number(2)
+		}
+	);
+
+	let error = from_json::<StrictPoint>(&seed, serde_json::json!({ "x": 11, "w": 0, "y": 12 }))
+		.unwrap_err()
+		.to_string();
+	assert_eq!(error, "unknown field `w`, expected `x` or `y`")
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub struct Labeled {
+	#[seeded(skip_serializing_if(Option::is_none))]
+	label: Option<String>,
+	#[seeded(with(serde_seeded::option), skip_serializing_if(Option::is_none))]
+	number: Option<Number>,
+	#[seeded(required)]
+	tag: Option<u32>,
+}
+
+#[test]
+fn missing_option_fields() {
+	let seed = Offset(10);
+
+	let value = Labeled {
+		label: None,
+		number: None,
+		tag: None,
+	};
+	let json = to_json(&seed, &value).unwrap();
+	assert_eq!(json, serde_json::json!({ "tag": null }));
+	assert_eq!(from_json::<Labeled>(&seed, json).unwrap(), value);
+
+	let value = Labeled {
+		label: Some("a".to_owned()),
+		number: Some(Number(1)),
+		tag: Some(2),
+	};
+	let json = to_json(&seed, &value).unwrap();
+	assert_eq!(
+		json,
+		serde_json::json!({ "label": "a", "number": 11, "tag": 2 })
+	);
+	assert_eq!(from_json::<Labeled>(&seed, json).unwrap(), value);
+
+	let error = from_json::<Labeled>(&seed, serde_json::json!({}))
+		.unwrap_err()
+		.to_string();
+	assert_eq!(error, "missing field `tag`")
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)), rename_all = "PascalCase")]
+pub struct Header {
+	content_length: Number,
+	#[seeded(rename = "x-custom")]
+	custom: u32,
+}
+
+#[test]
+fn rename_all() {
+	let seed = Offset(10);
+	let value = Header {
+		content_length: Number(1),
+		custom: 2,
+	};
+
+	let json = to_json(&seed, &value).unwrap();
+	assert_eq!(
+		json,
+		serde_json::json!({ "ContentLength": 11, "x-custom": 2 })
+	);
+	assert_eq!(from_json::<Header>(&seed, json).unwrap(), value)
+}
+
+fn default_version() -> u32 {
+	3
+}
+
+#[derive(Debug, PartialEq, DeserializeSeeded)]
+#[seeded(de(seed(Offset)))]
+pub struct Versioned {
+	#[seeded(default = default_version)]
+	version: u32,
+	#[seeded(default_seeded = "common::offset")]
+	offset: u32,
+	#[seeded(skip, default_seeded(common::offset))]
+	cached_offset: u32,
+}
+
+#[test]
+fn default_values() {
+	let seed = Offset(10);
+
+	assert_eq!(
+		from_json::<Versioned>(&seed, serde_json::json!({})).unwrap(),
+		Versioned {
+			version: 3,
+			offset: 10,
+			cached_offset: 10
+		}
+	);
+
+	assert_eq!(
+		from_json::<Versioned>(&seed, serde_json::json!({ "version": 1, "offset": 2 })).unwrap(),
+		Versioned {
+			version: 1,
+			offset: 2,
+			cached_offset: 10
+		}
+	);
+
+	assert_eq!(
+		from_json::<Versioned>(&seed, serde_json::json!([1])).unwrap(),
+		Versioned {
+			version: 1,
+			offset: 10,
+			cached_offset: 10
+		}
+	);
+}
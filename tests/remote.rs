@@ -0,0 +1,211 @@
+use serde_seeded::{DeserializeSeeded, SerializeSeeded};
+
+mod common;
+use common::{deserialize_offset, from_json, serialize_offset, to_json, Offset};
+
+/// Types defined in another crate.
+mod other {
+	pub struct Span {
+		pub start: u32,
+		pub end: u32,
+	}
+
+	pub enum Kind {
+		Empty,
+		Ident(String),
+		Group { open: u32, close: u32 },
+	}
+
+	pub struct Index(u32);
+
+	impl Index {
+		pub fn new(index: u32) -> Self {
+			Self(index)
+		}
+
+		pub fn index(&self) -> u32 {
+			self.0
+		}
+	}
+}
+
+#[derive(SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)), remote = "other::Span")]
+pub struct SpanDef {
+	#[seeded(serialize_with_seeded = serialize_offset, deserialize_with_seeded = deserialize_offset)]
+	start: u32,
+	#[seeded(serialize_with_seeded = serialize_offset, deserialize_with_seeded = deserialize_offset)]
+	end: u32,
+}
+
+#[derive(SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)), remote(other::Kind))]
+pub enum KindDef {
+	Empty,
+	Ident(String),
+	Group {
+		#[seeded(serialize_with_seeded = serialize_offset, deserialize_with_seeded = deserialize_offset)]
+		open: u32,
+		close: u32,
+	},
+}
+
+/// Built through `From`, since the remote field is private.
+#[derive(SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)), remote = "other::Index")]
+pub struct IndexDef(
+	#[seeded(
+		getter = other::Index::index,
+		serialize_with_seeded = serialize_offset,
+		deserialize_with_seeded = deserialize_offset
+	)]
+	u32,
+);
+
+impl From<IndexDef> for other::Index {
+	fn from(value: IndexDef) -> Self {
+		Self::new(value.0)
+	}
+}
+
+#[derive(SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub struct Token {
+	#[seeded(with = SpanDef)]
+	span: other::Span,
+	#[seeded(with = KindDef)]
+	kind: other::Kind,
+	#[seeded(with = IndexDef)]
+	index: other::Index,
+}
+
+#[derive(SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub enum Node {
+	Leaf(#[seeded(with = SpanDef)] other::Span),
+	Branch {
+		#[seeded(with = IndexDef)]
+		left: other::Index,
+		#[seeded(with = IndexDef)]
+		right: other::Index,
+	},
+}
+
+#[test]
+fn remote() {
+	let seed = Offset(10);
+
+	let token = Token {
+		span: other::Span { start: 1, end: 2 },
+		kind: other::Kind::Group { open: 3, close: 4 },
+		index: other::Index::new(5),
+	};
+	let json = to_json(&seed, &token).unwrap();
+	assert_eq!(
+		json,
+		serde_json::json!({
+			"span": { "start": 11, "end": 12 },
+			"kind": { "Group": { "open": 13, "close": 4 } },
+			"index": 15
+		})
+	);
+
+	let token: Token = from_json(&seed, json).unwrap();
+	assert_eq!((token.span.start, token.span.end), (1, 2));
+	assert!(matches!(
+		token.kind,
+		other::Kind::Group { open: 3, close: 4 }
+	));
+	assert_eq!(token.index.index(), 5);
+
+	let token: Token = from_json(
+		&seed,
+		serde_json::json!({
+			"span": { "start": 11, "end": 11 },
+			"kind": { "Ident": "foo" },
+			"index": 10
+		}),
+	)
+	.unwrap();
+	assert!(matches!(token.kind, other::Kind::Ident(name) if name == "foo"));
+
+	let json = to_json(
+		&seed,
+		&Token {
+			span: other::Span { start: 0, end: 0 },
+			kind: other::Kind::Empty,
+			index: other::Index::new(0),
+		},
+	)
+	.unwrap();
+	assert_eq!(json["kind"], serde_json::json!("Empty"));
+}
+
+#[test]
+fn remote_getter_round_trip() {
+	let seed = Offset(10);
+
+	let mut serializer = serde_json::Serializer::new(Vec::new());
+	IndexDef::serialize_seeded(&other::Index::new(7), &seed, &mut serializer).unwrap();
+	let json = String::from_utf8(serializer.into_inner()).unwrap();
+	assert_eq!(json, "17");
+
+	let mut deserializer = serde_json::Deserializer::from_str(&json);
+	let index = IndexDef::deserialize_seeded(&seed, &mut deserializer).unwrap();
+	assert_eq!(index.index(), 7);
+}
+
+#[test]
+fn remote_edge_cases() {
+	let seed = Offset(10);
+
+	// Remote types in variants.
+	let json = to_json(&seed, &Node::Leaf(other::Span { start: 1, end: 2 })).unwrap();
+	assert_eq!(
+		json,
+		serde_json::json!({ "Leaf": { "start": 11, "end": 12 } })
+	);
+	let node: Node = from_json(&seed, json).unwrap();
+	assert!(matches!(node, Node::Leaf(other::Span { start: 1, end: 2 })));
+
+	let json = to_json(
+		&seed,
+		&Node::Branch {
+			left: other::Index::new(1),
+			right: other::Index::new(2),
+		},
+	)
+	.unwrap();
+	assert_eq!(
+		json,
+		serde_json::json!({ "Branch": { "left": 11, "right": 12 } })
+	);
+	let node: Node = from_json(&seed, json).unwrap();
+	assert!(
+		matches!(node, Node::Branch { left, right } if (left.index(), right.index()) == (1, 2))
+	);
+
+	// Errors of the remote definitions are forwarded.
+	let error = from_json::<Node>(
+		&seed,
+		serde_json::json!({ "Leaf": { "start": 1, "end": 12 } }),
+	)
+	.err()
+	.unwrap();
+	assert_eq!(error.to_string(), "number below the offset");
+
+	let error = from_json::<Token>(
+		&seed,
+		serde_json::json!({
+			"span": { "start": 11, "end": 12 },
+			"kind": { "Unknown": 0 },
+			"index": 10
+		}),
+	)
+	.err()
+	.unwrap();
+	assert_eq!(
+		error.to_string(),
+		"unknown variant `Unknown`, expected one of `Empty`, `Ident`, `Group`"
+	);
+}
@@ -0,0 +1,225 @@
+use serde::de::{DeserializeSeed, Error};
+use serde_seeded::{
+	context::Provide, de::Seed, ser::SeededRef, DeserializeSeeded, SeedContext, SerializeSeeded,
+};
+
+/// Names known by the application.
+pub struct Interner {
+	names: Vec<&'static str>,
+}
+
+/// Units known by the application.
+pub struct UnitRegistry {
+	units: Vec<&'static str>,
+}
+
+/// Indexes `name` into `names`, or fails with an error.
+fn index_of<E: Error>(names: &[&str], name: &str) -> Result<usize, E> {
+	names
+		.iter()
+		.position(|n| *n == name)
+		.ok_or_else(|| E::custom(format!("unknown name `{name}`")))
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Symbol(usize);
+
+impl SerializeSeeded<Interner> for Symbol {
+	fn serialize_seeded<S>(&self, seed: &Interner, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serializer.serialize_str(seed.names[self.0])
+	}
+}
+
+impl<'de> DeserializeSeeded<'de, Interner> for Symbol {
+	fn deserialize_seeded<D>(seed: &Interner, deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let name = String::deserialize_seeded(&(), deserializer)?;
+		index_of(&seed.names, &name).map(Self)
+	}
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Unit(usize);
+
+impl SerializeSeeded<UnitRegistry> for Unit {
+	fn serialize_seeded<S>(&self, seed: &UnitRegistry, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serializer.serialize_str(seed.units[self.0])
+	}
+}
+
+impl<'de> DeserializeSeeded<'de, UnitRegistry> for Unit {
+	fn deserialize_seeded<D>(seed: &UnitRegistry, deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let name = String::deserialize_seeded(&(), deserializer)?;
+		index_of(&seed.units, &name).map(Self)
+	}
+}
+
+/// Only requires the interner.
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(interner: Interner)))]
+pub struct Variable {
+	#[seeded(seed = interner)]
+	name: Symbol,
+}
+
+/// Only requires the unit registry.
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(units: UnitRegistry)))]
+pub struct Quantity {
+	value: u32,
+	#[seeded(seed = units)]
+	unit: Unit,
+}
+
+/// Composes types requiring different components.
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(interner: Interner, units: UnitRegistry)))]
+pub struct Measurement {
+	variable: Variable,
+	quantity: Quantity,
+	#[seeded(seed = interner, with(serde_seeded::option))]
+	label: Option<Symbol>,
+}
+
+/// Application context providing its components statically.
+pub struct AppContext {
+	interner: Interner,
+	units: UnitRegistry,
+}
+
+impl Provide<Interner> for AppContext {
+	fn provide(&self) -> Option<&Interner> {
+		Some(&self.interner)
+	}
+}
+
+impl Provide<UnitRegistry> for AppContext {
+	fn provide(&self) -> Option<&UnitRegistry> {
+		Some(&self.units)
+	}
+}
+
+fn interner() -> Interner {
+	Interner {
+		names: vec!["x", "speed"],
+	}
+}
+
+fn units() -> UnitRegistry {
+	UnitRegistry {
+		units: vec!["m", "km/h"],
+	}
+}
+
+fn measurement() -> Measurement {
+	Measurement {
+		variable: Variable { name: Symbol(0) },
+		quantity: Quantity {
+			value: 90,
+			unit: Unit(1),
+		},
+		label: Some(Symbol(1)),
+	}
+}
+
+const MEASUREMENT: &str =
+	r#"{"variable":{"name":"x"},"quantity":{"value":90,"unit":"km/h"},"label":"speed"}"#;
+
+#[test]
+fn seed_context() {
+	let context = SeedContext::new().with(interner()).with(units());
+
+	assert_eq!(
+		serde_json::to_string(&SeededRef::new(&context, &measurement())).unwrap(),
+		MEASUREMENT
+	);
+
+	let value: Measurement = Seed::new(&context)
+		.deserialize(&mut serde_json::Deserializer::from_str(MEASUREMENT))
+		.unwrap();
+	assert_eq!(value, measurement());
+}
+
+#[test]
+fn static_context() {
+	let context = AppContext {
+		interner: interner(),
+		units: units(),
+	};
+
+	assert_eq!(
+		serde_json::to_string(&SeededRef::new(&context, &measurement())).unwrap(),
+		MEASUREMENT
+	);
+
+	let value: Measurement = Seed::new(&context)
+		.deserialize(&mut serde_json::Deserializer::from_str(MEASUREMENT))
+		.unwrap();
+	assert_eq!(value, measurement());
+}
+
+#[test]
+fn shared_context() {
+	let context = SeedContext::new().with(interner()).with(units());
+
+	std::thread::scope(|scope| {
+		let threads: Vec<_> = (0..2)
+			.map(|_| {
+				scope.spawn(|| serde_json::to_string(&SeededRef::new(&context, &measurement())))
+			})
+			.collect();
+
+		for thread in threads {
+			assert_eq!(thread.join().unwrap().unwrap(), MEASUREMENT);
+		}
+	});
+}
+
+#[test]
+fn partial_context() {
+	let mut context = SeedContext::new().with(interner());
+	assert!(context.contains::<Interner>());
+	assert!(context.get::<UnitRegistry>().is_none());
+
+	let variable = Variable { name: Symbol(1) };
+	assert_eq!(
+		serde_json::to_string(&SeededRef::new(&context, &variable)).unwrap(),
+		r#"{"name":"speed"}"#
+	);
+
+	context.get_mut::<Interner>().unwrap().names.push("y");
+	let variable: Variable = Seed::new(&context)
+		.deserialize(&mut serde_json::Deserializer::from_str(r#"{"name":"y"}"#))
+		.unwrap();
+	assert_eq!(variable, Variable { name: Symbol(2) });
+}
+
+#[test]
+fn missing_component() {
+	let context = SeedContext::new().with(interner());
+	let quantity = Quantity {
+		value: 1,
+		unit: Unit(0),
+	};
+
+	let error = serde_json::to_string(&SeededRef::new(&context, &quantity)).unwrap_err();
+	assert!(error.to_string().contains("missing seed component"));
+
+	let error = Seed::<_, Quantity>::new(&context)
+		.deserialize(&mut serde_json::Deserializer::from_str(
+			r#"{"value":1,"unit":"m"}"#,
+		))
+		.unwrap_err();
+	assert!(error.to_string().contains("missing seed component"));
+}
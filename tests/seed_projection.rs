@@ -0,0 +1,141 @@
+use serde::de::DeserializeSeed;
+use serde_seeded::{de::Seed, ser::SeededRef, DeserializeSeeded, SerializeSeeded};
+
+mod common;
+use common::{Number, Offset};
+
+/// Large context, only a part of which is needed by some fields.
+pub struct Context {
+	offset: Offset,
+	big_offset: Offset,
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Context)))]
+pub struct Point {
+	#[seeded(seed = &seed.offset)]
+	x: Number,
+	#[seeded(seed(&seed.big_offset))]
+	y: Number,
+	#[seeded(seed = &seed.offset, with(serde_seeded::option))]
+	z: Option<Number>,
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Context)))]
+pub struct Pair(
+	#[seeded(seed = &seed.offset)] Number,
+	#[seeded(seed = &seed.big_offset)] Number,
+);
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Context)), transparent)]
+pub struct Wrapper(#[seeded(seed = &seed.big_offset)] Number);
+
+#[test]
+fn seed_projection() {
+	let seed = Context {
+		offset: Offset(10),
+		big_offset: Offset(100),
+	};
+
+	let point = Point {
+		x: Number(1),
+		y: Number(2),
+		z: Some(Number(3)),
+	};
+	let json = serde_json::to_value(SeededRef::new(&seed, &point)).unwrap();
+	assert_eq!(json, serde_json::json!({ "x": 11, "y": 102, "z": 13 }));
+	assert_eq!(
+		Seed::<_, Point>::new(&seed).deserialize(json).unwrap(),
+		point
+	);
+
+	let pair = Pair(Number(1), Number(2));
+	let json = serde_json::to_value(SeededRef::new(&seed, &pair)).unwrap();
+	assert_eq!(json, serde_json::json!([11, 102]));
+	assert_eq!(Seed::<_, Pair>::new(&seed).deserialize(json).unwrap(), pair);
+
+	let wrapper = Wrapper(Number(1));
+	let json = serde_json::to_value(SeededRef::new(&seed, &wrapper)).unwrap();
+	assert_eq!(json, serde_json::json!(101));
+	assert_eq!(
+		Seed::<_, Wrapper>::new(&seed).deserialize(json).unwrap(),
+		wrapper
+	);
+}
+
+/// Only requires the offset.
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub struct Span {
+	start: Number,
+	end: Number,
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Context)), tag = "type")]
+pub enum Item {
+	Span(#[seeded(seed = &seed.offset)] Span),
+	Labeled {
+		#[seeded(flatten, seed = &seed.big_offset)]
+		span: Span,
+		#[seeded(seed = &())]
+		label: String,
+	},
+}
+
+#[test]
+fn seed_projection_edge_cases() {
+	let seed = Context {
+		offset: Offset(10),
+		big_offset: Offset(100),
+	};
+
+	let values = [
+		(
+			Item::Span(Span {
+				start: Number(1),
+				end: Number(2),
+			}),
+			serde_json::json!({ "type": "Span", "start": 11, "end": 12 }),
+		),
+		(
+			Item::Labeled {
+				span: Span {
+					start: Number(1),
+					end: Number(2),
+				},
+				label: "a".to_owned(),
+			},
+			serde_json::json!({ "type": "Labeled", "start": 101, "end": 102, "label": "a" }),
+		),
+	];
+
+	for (value, expected) in values {
+		let json = serde_json::to_value(SeededRef::new(&seed, &value)).unwrap();
+		assert_eq!(json, expected);
+		assert_eq!(
+			Seed::<_, Item>::new(&seed).deserialize(json).unwrap(),
+			value
+		)
+	}
+
+	let point = Point {
+		x: Number(1),
+		y: Number(2),
+		z: None,
+	};
+	let json = serde_json::to_value(SeededRef::new(&seed, &point)).unwrap();
+	assert_eq!(json, serde_json::json!({ "x": 11, "y": 102, "z": null }));
+	assert_eq!(
+		Seed::<_, Point>::new(&seed).deserialize(json).unwrap(),
+		point
+	);
+
+	// Each field is checked against its own projected seed.
+	let error = Seed::<_, Point>::new(&seed)
+		.deserialize(serde_json::json!({ "x": 11, "y": 12 }))
+		.unwrap_err();
+	assert_eq!(error.to_string(), "number below the offset");
+}
@@ -0,0 +1,111 @@
+use serde_seeded::{ser::SeededRef, SerializeSeeded};
+
+/// Export target, telling which fields are supported.
+pub struct Target {
+	default_color: u32,
+	supports_alpha: bool,
+}
+
+fn is_default_color(color: &u32, target: &Target) -> bool {
+	*color == target.default_color
+}
+
+fn alpha_unsupported(_: &u8, target: &Target) -> bool {
+	!target.supports_alpha
+}
+
+fn is_zero(value: &u32) -> bool {
+	*value == 0
+}
+
+#[derive(SerializeSeeded)]
+#[seeded(ser(seed(Target)))]
+pub struct Style {
+	#[seeded(skip_serializing_if_seeded = is_default_color)]
+	color: u32,
+	#[seeded(skip_serializing_if_seeded = alpha_unsupported)]
+	alpha: u8,
+	#[seeded(skip_serializing_if = is_zero, skip_serializing_if_seeded = is_default_color)]
+	border: u32,
+}
+
+#[derive(SerializeSeeded)]
+#[seeded(ser(seed(Target)))]
+pub struct Rgba(
+	u32,
+	#[seeded(skip_serializing_if_seeded = alpha_unsupported)] u8,
+);
+
+#[derive(SerializeSeeded)]
+#[seeded(ser(seed(Target)))]
+pub enum Fill {
+	Solid(
+		#[seeded(skip_serializing_if_seeded = is_default_color)] u32,
+		#[seeded(skip_serializing_if_seeded = alpha_unsupported)] u8,
+	),
+}
+
+#[test]
+fn skip_serializing_if_seeded() {
+	let target = Target {
+		default_color: 0xff,
+		supports_alpha: false,
+	};
+
+	let style = Style {
+		color: 0xff,
+		alpha: 128,
+		border: 2,
+	};
+	assert_eq!(
+		serde_json::to_value(SeededRef::new(&target, &style)).unwrap(),
+		serde_json::json!({ "border": 2 })
+	);
+
+	let style = Style {
+		color: 1,
+		alpha: 128,
+		border: 0,
+	};
+	assert_eq!(
+		serde_json::to_value(SeededRef::new(&target, &style)).unwrap(),
+		serde_json::json!({ "color": 1 })
+	);
+
+	let target = Target {
+		default_color: 0xff,
+		supports_alpha: true,
+	};
+	assert_eq!(
+		serde_json::to_value(SeededRef::new(&target, &style)).unwrap(),
+		serde_json::json!({ "color": 1, "alpha": 128 })
+	);
+}
+
+#[test]
+fn skip_serializing_tuple_fields() {
+	let mut target = Target {
+		default_color: 0xff,
+		supports_alpha: true,
+	};
+
+	assert_eq!(
+		serde_json::to_value(SeededRef::new(&target, &Rgba(1, 128))).unwrap(),
+		serde_json::json!([1, 128])
+	);
+	assert_eq!(
+		serde_json::to_value(SeededRef::new(&target, &Fill::Solid(0xff, 128))).unwrap(),
+		serde_json::json!({ "Solid": [128] })
+	);
+
+	target.supports_alpha = false;
+
+	assert_eq!(
+		serde_json::to_value(SeededRef::new(&target, &Rgba(1, 128))).unwrap(),
+		serde_json::json!([1])
+	);
+	assert_eq!(
+		serde_json::to_value(SeededRef::new(&target, &Fill::Solid(1, 128))).unwrap(),
+		serde_json::json!({ "Solid": [1] })
+	);
+}
@@ -0,0 +1,99 @@
+use serde::de::DeserializeSeed;
+use serde_seeded::{de::Seed, ser::SeededRef, DeserializeSeeded, SerializeSeeded};
+
+/// Context supplied by plugins.
+pub trait Resolver {
+	fn name(&self, id: u32) -> &str;
+
+	fn id(&self, name: &str) -> Option<u32>;
+}
+
+/// Resolver using a list of names.
+pub struct Names(Vec<String>);
+
+impl Resolver for Names {
+	fn name(&self, id: u32) -> &str {
+		&self.0[id as usize]
+	}
+
+	fn id(&self, name: &str) -> Option<u32> {
+		self.0.iter().position(|n| n == name).map(|i| i as u32)
+	}
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Symbol(u32);
+
+impl SerializeSeeded<dyn Resolver> for Symbol {
+	fn serialize_seeded<S>(&self, seed: &dyn Resolver, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serializer.serialize_str(seed.name(self.0))
+	}
+}
+
+impl<'de> DeserializeSeeded<'de, dyn Resolver> for Symbol {
+	fn deserialize_seeded<D>(seed: &dyn Resolver, deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let name: String = serde::Deserialize::deserialize(deserializer)?;
+		seed.id(&name)
+			.map(Self)
+			.ok_or_else(|| serde::de::Error::custom(format!("unknown symbol `{name}`")))
+	}
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(dyn Resolver)))]
+pub struct Call {
+	function: Symbol,
+	#[seeded(with(serde_seeded::seq))]
+	args: Vec<Symbol>,
+	#[seeded(with(serde_seeded::option))]
+	result: Option<Symbol>,
+}
+
+/// Index into a slice seed.
+#[derive(Debug, PartialEq)]
+pub struct Index(usize);
+
+impl SerializeSeeded<[&str]> for Index {
+	fn serialize_seeded<S>(&self, seed: &[&str], serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serializer.serialize_str(seed[self.0])
+	}
+}
+
+/// Generic over the seed type, including unsized ones.
+#[derive(Debug, PartialEq, SerializeSeeded)]
+pub struct Pair<T>(T, T);
+
+#[test]
+fn trait_object_seed() {
+	let names = Names(vec!["f".to_owned(), "x".to_owned(), "y".to_owned()]);
+	let seed: &dyn Resolver = &names;
+
+	let call = Call {
+		function: Symbol(0),
+		args: vec![Symbol(1), Symbol(2)],
+		result: None,
+	};
+	let json = serde_json::to_value(SeededRef::new(seed, &call)).unwrap();
+	assert_eq!(
+		json,
+		serde_json::json!({ "function": "f", "args": ["x", "y"], "result": null })
+	);
+	assert_eq!(Seed::<_, Call>::new(seed).deserialize(json).unwrap(), call);
+}
+
+#[test]
+fn slice_seed() {
+	let seed: &[&str] = &["a", "b"];
+
+	let json = serde_json::to_value(SeededRef::new(seed, &Pair(Index(1), Index(0)))).unwrap();
+	assert_eq!(json, serde_json::json!(["b", "a"]));
+}
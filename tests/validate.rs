@@ -0,0 +1,81 @@
+use serde::de::DeserializeSeed;
+use serde_seeded::{de::Seed, DeserializeSeeded};
+
+/// Seed listing the known identifiers.
+pub struct Registry(Vec<u32>);
+
+fn check_id(item: &Item, registry: &Registry) -> Result<(), String> {
+	if registry.0.contains(&item.id) {
+		Ok(())
+	} else {
+		Err(format!("unknown id {}", item.id))
+	}
+}
+
+fn check_shape(shape: &Shape, _registry: &Registry) -> Result<(), &'static str> {
+	match shape {
+		Shape::Rect { width, height } if width != height => Err("expected a square"),
+		_ => Ok(()),
+	}
+}
+
+#[derive(Debug, PartialEq, DeserializeSeeded)]
+#[seeded(de(seed(Registry)), validate = check_id)]
+pub struct Item {
+	id: u32,
+}
+
+#[derive(Debug, PartialEq, DeserializeSeeded)]
+#[seeded(de(seed(Registry)), validate(check_shape))]
+pub enum Shape {
+	Empty,
+	Rect { width: u32, height: u32 },
+}
+
+fn from_json<T>(seed: &Registry, json: serde_json::Value) -> Result<T, serde_json::Error>
+where
+	T: for<'de> DeserializeSeeded<'de, Registry>,
+{
+	Seed::new(seed).deserialize(json)
+}
+
+#[test]
+fn validate() {
+	let seed = Registry(vec![1, 2]);
+
+	assert_eq!(
+		from_json::<Item>(&seed, serde_json::json!({ "id": 1 })).unwrap(),
+		Item { id: 1 }
+	);
+	assert_eq!(
+		from_json::<Item>(&seed, serde_json::json!({ "id": 3 }))
+			.unwrap_err()
+			.to_string(),
+		"unknown id 3"
+	);
+
+	assert_eq!(
+		from_json::<Shape>(&seed, serde_json::json!("Empty")).unwrap(),
+		Shape::Empty
+	);
+	assert_eq!(
+		from_json::<Shape>(
+			&seed,
+			serde_json::json!({ "Rect": { "width": 1, "height": 1 } })
+		)
+		.unwrap(),
+		Shape::Rect {
+			width: 1,
+			height: 1
+		}
+	);
+	assert_eq!(
+		from_json::<Shape>(
+			&seed,
+			serde_json::json!({ "Rect": { "width": 1, "height": 2 } })
+		)
+		.unwrap_err()
+		.to_string(),
+		"expected a square"
+	);
+}
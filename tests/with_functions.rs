@@ -0,0 +1,156 @@
+use serde_seeded::{DeserializeSeeded, SerializeSeeded};
+
+mod common;
+use common::{deserialize_offset, from_json, serialize_offset, to_json, Offset};
+
+/// `serde` `with` module serializing bytes as an hexadecimal string.
+mod hex {
+	use serde::{Deserialize, Deserializer, Serializer};
+
+	pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+		let string: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+		serializer.serialize_str(&string)
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+		let string = String::deserialize(deserializer)?;
+		(0..string.len())
+			.step_by(2)
+			.map(|i| u8::from_str_radix(&string[i..i + 2], 16).map_err(serde::de::Error::custom))
+			.collect()
+	}
+}
+
+fn serialize_doubled<S: serde::Serializer>(value: &u32, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_u32(value * 2)
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub struct Packet {
+	#[seeded(with_unseeded(hex))]
+	payload: Vec<u8>,
+	#[seeded(serialize_with_seeded = serialize_offset, deserialize_with_seeded = deserialize_offset)]
+	id: u32,
+	#[seeded(serialize_with = serialize_doubled)]
+	size: u32,
+}
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub struct Checksum(
+	#[seeded(serialize_with = hex::serialize, deserialize_with = hex::deserialize)] Vec<u8>,
+	#[seeded(serialize_with_seeded = serialize_offset, deserialize_with_seeded = deserialize_offset)]
+	 u32,
+);
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)), transparent)]
+pub struct Id(
+	#[seeded(serialize_with_seeded = serialize_offset, deserialize_with_seeded = deserialize_offset)]
+	 u32,
+);
+
+#[derive(Debug, PartialEq, SerializeSeeded, DeserializeSeeded)]
+#[seeded(serde(seed(Offset)))]
+pub enum Message {
+	Ping(
+		#[seeded(serialize_with_seeded = serialize_offset, deserialize_with_seeded = deserialize_offset)]
+		 u32,
+	),
+	Data {
+		#[seeded(with_unseeded(hex))]
+		payload: Vec<u8>,
+		#[seeded(serialize_with = serialize_doubled)]
+		size: u32,
+	},
+}
+
+#[test]
+fn with_functions() {
+	let seed = Offset(10);
+
+	let packet = Packet {
+		payload: vec![0xca, 0xfe],
+		id: 1,
+		size: 2,
+	};
+	let json = to_json(&seed, &packet).unwrap();
+	assert_eq!(
+		json,
+		serde_json::json!({ "payload": "cafe", "id": 11, "size": 4 })
+	);
+	assert_eq!(
+		from_json::<Packet>(
+			&seed,
+			serde_json::json!({ "payload": "cafe", "id": 11, "size": 2 })
+		)
+		.unwrap(),
+		packet
+	);
+
+	let checksum = Checksum(vec![0xbe, 0xef], 1);
+	let json = to_json(&seed, &checksum).unwrap();
+	assert_eq!(json, serde_json::json!(["beef", 11]));
+	assert_eq!(from_json::<Checksum>(&seed, json).unwrap(), checksum);
+
+	let json = to_json(&seed, &Id(1)).unwrap();
+	assert_eq!(json, serde_json::json!(11));
+	assert_eq!(from_json::<Id>(&seed, json).unwrap(), Id(1));
+}
+
+#[test]
+fn with_functions_edge_cases() {
+	let seed = Offset(10);
+
+	// Functions are also used when reading a struct from a sequence.
+	assert_eq!(
+		from_json::<Packet>(&seed, serde_json::json!(["cafe", 11, 2])).unwrap(),
+		Packet {
+			payload: vec![0xca, 0xfe],
+			id: 1,
+			size: 2,
+		}
+	);
+
+	// Variant fields.
+	let values = [
+		(Message::Ping(1), serde_json::json!({ "Ping": 11 })),
+		(
+			Message::Data {
+				payload: vec![0x01],
+				size: 3,
+			},
+			serde_json::json!({ "Data": { "payload": "01", "size": 6 } }),
+		),
+	];
+	for (value, expected) in values {
+		assert_eq!(to_json(&seed, &value).unwrap(), expected);
+	}
+	assert_eq!(
+		from_json::<Message>(&seed, serde_json::json!({ "Ping": 11 })).unwrap(),
+		Message::Ping(1)
+	);
+	assert_eq!(
+		from_json::<Message>(
+			&seed,
+			serde_json::json!({ "Data": { "payload": "01", "size": 3 } })
+		)
+		.unwrap(),
+		Message::Data {
+			payload: vec![0x01],
+			size: 3
+		}
+	);
+
+	// Errors of the functions are forwarded.
+	let error = from_json::<Packet>(
+		&seed,
+		serde_json::json!({ "payload": "zz", "id": 11, "size": 2 }),
+	)
+	.unwrap_err();
+	assert_eq!(error.to_string(), "invalid digit found in string");
+
+	let error = from_json::<Id>(&seed, serde_json::json!(1)).unwrap_err();
+	assert_eq!(error.to_string(), "number below the offset");
+}